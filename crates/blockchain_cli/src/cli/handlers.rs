@@ -1,41 +1,65 @@
 use blockchain_core::config::NodeConfig;
-use serde::Deserialize;
-use std::fs;
-
-#[derive(Debug, Deserialize)]
-struct FileConfig {
-    difficulty: Option<usize>,
-}
 
 pub fn load_config(path: Option<&str>) -> Result<NodeConfig, String> {
-    let mut config = NodeConfig::default();
-
-    if let Some(path) = path {
-        let raw = fs::read_to_string(path)
-            .map_err(|e| format!("не удалось прочитать файл {path}: {e}"))?;
-        let file_config: FileConfig =
-            toml::from_str(&raw).map_err(|e| format!("невалидный TOML в {path}: {e}"))?;
+    let Some(path) = path else {
+        return Ok(NodeConfig::default());
+    };
 
-        if let Some(difficulty) = file_config.difficulty {
-            config = NodeConfig::new(difficulty);
-        }
-    }
-
-    Ok(config)
+    NodeConfig::from_file(path).map_err(|e| format!("не удалось загрузить {path}: {e}"))
 }
 
 pub fn handle_init(config: &NodeConfig) {
-    println!("init: difficulty={}", config.difficulty);
+    println!("init: initial_bits={:#010x}", config.initial_bits);
 }
 
 pub fn handle_add_tx(config: &NodeConfig) {
-    println!("add-tx: difficulty={}", config.difficulty);
+    println!("add-tx: initial_bits={:#010x}", config.initial_bits);
 }
 
 pub fn handle_mine(config: &NodeConfig) {
-    println!("mine: difficulty={}", config.difficulty);
+    println!("mine: initial_bits={:#010x}", config.initial_bits);
 }
 
 pub fn handle_print(config: &NodeConfig) {
-    println!("print: difficulty={}", config.difficulty);
+    println!("print: initial_bits={:#010x}", config.initial_bits);
+}
+
+#[cfg(test)]
+mod tests {
+    use blockchain_core::types::Address;
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn file_config_overrides_mempool_and_reward_settings() {
+        let path = std::env::temp_dir().join(format!("blockchain-cli-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            r#"
+                miner_address = "0x0909090909090909090909090909090909090909"
+                block_subsidy = 50
+                halving_interval = 10
+                genesis_alloc = [["0x0202020202020202020202020202020202020202", 1000]]
+                price_bump_percent = 25
+                max_transactions = 8
+                max_per_sender = 2
+            "#,
+        )
+        .unwrap();
+        let config = load_config(path.to_str());
+        let _ = fs::remove_file(&path);
+        let config = config.unwrap();
+
+        assert_eq!(config.miner_address, Address::from_bytes([9_u8; 20]));
+        assert_eq!(config.block_subsidy, 50);
+        assert_eq!(config.halving_interval, 10);
+        assert_eq!(
+            config.genesis_alloc,
+            [(Address::from_bytes([2_u8; 20]), 1000)]
+        );
+        assert_eq!(config.mempool.price_bump_percent, 25);
+        assert_eq!(config.mempool.max_transactions, 8);
+        assert_eq!(config.mempool.max_per_sender, 2);
+    }
 }
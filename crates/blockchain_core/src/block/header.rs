@@ -0,0 +1,42 @@
+use rlp::RlpStream;
+
+use crate::types::{Address, Hash, Timestamp};
+
+#[derive(Debug, Clone)]
+pub struct BlockHeader {
+    pub parent_hash: Hash,
+    pub number: u64,
+    pub timestamp: Timestamp,
+    pub transactions_root: Hash,
+    pub state_root: Hash,
+    pub bits: u32,
+    pub nonce: u64,
+    pub extra_nonce: u64,
+    pub miner: Address,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub base_fee_per_gas: u64,
+}
+
+impl BlockHeader {
+    pub fn hash(&self) -> Hash {
+        Hash::keccak(&self.payload())
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(12);
+        stream.append(&self.parent_hash.as_bytes().to_vec());
+        stream.append(&self.number);
+        stream.append(&self.timestamp.as_u64());
+        stream.append(&self.transactions_root.as_bytes().to_vec());
+        stream.append(&self.state_root.as_bytes().to_vec());
+        stream.append(&self.bits);
+        stream.append(&self.nonce);
+        stream.append(&self.extra_nonce);
+        stream.append(&self.miner.as_bytes().to_vec());
+        stream.append(&self.gas_used);
+        stream.append(&self.gas_limit);
+        stream.append(&self.base_fee_per_gas);
+        stream.out().to_vec()
+    }
+}
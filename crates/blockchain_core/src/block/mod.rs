@@ -1,34 +1,146 @@
+pub mod header;
+
+pub use header::*;
+
+use crate::config::NodeConfig;
+use crate::error::BlockError;
+use crate::merkle::{self, MerkleProof};
+use crate::state::WorldState;
 use crate::transaction::Transaction;
-use crate::types::{Hash, Timestamp};
+use crate::types::{Address, Hash, Timestamp};
 
 #[derive(Debug, Clone)]
 pub struct Block {
-    pub index: u64,
-    pub previous_hash: Hash,
-    pub timestamp: Timestamp,
+    pub header: BlockHeader,
     pub transactions: Vec<Transaction>,
-    pub nonce: u64,
-    pub hash: Hash,
 }
 
 impl Block {
-    pub fn genesis(timestamp: Timestamp) -> Self {
-        let _ = timestamp;
-        todo!("genesis block implementation is not added yet")
+    pub fn genesis(config: &NodeConfig) -> Self {
+        let header = BlockHeader {
+            parent_hash: Hash::zero(),
+            number: 0,
+            timestamp: Timestamp::new(config.genesis_timestamp),
+            transactions_root: Hash::zero(),
+            state_root: WorldState::genesis(config).state_root(),
+            bits: config.initial_bits,
+            nonce: 0,
+            extra_nonce: 0,
+            miner: Address::zero(),
+            gas_used: 0,
+            gas_limit: config.block_gas_limit,
+            base_fee_per_gas: config.initial_base_fee,
+        };
+        Self::new(header, Vec::new())
+    }
+
+    pub fn new(mut header: BlockHeader, transactions: Vec<Transaction>) -> Self {
+        header.transactions_root = Self::calculate_transactions_root(&transactions);
+        Self {
+            header,
+            transactions,
+        }
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash()
+    }
+
+    pub fn number(&self) -> u64 {
+        self.header.number
+    }
+
+    pub fn calculate_transactions_root(transactions: &[Transaction]) -> Hash {
+        let leaves: Vec<Hash> = transactions
+            .iter()
+            .map(|transaction| transaction.hash)
+            .collect();
+        merkle::merkle_root(&leaves)
+    }
+
+    pub fn transaction_proof(&self, transaction_hash: &Hash) -> Option<MerkleProof> {
+        let leaves: Vec<Hash> = self
+            .transactions
+            .iter()
+            .map(|transaction| transaction.hash)
+            .collect();
+        let index = leaves.iter().position(|hash| hash == transaction_hash)?;
+        merkle::build_proof(&leaves, index)
+    }
+
+    pub fn validate_body(&self) -> Result<(), BlockError> {
+        if Self::calculate_transactions_root(&self.transactions) != self.header.transactions_root {
+            return Err(BlockError::InvalidTransactionsRoot);
+        }
+
+        Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT_GENESIS_HASH: &str =
+        "0x3bb2661a6778eae30f40654e6f4458dfb497e9df7ce7dd57784cb4d09f150d4e";
+
+    #[test]
+    fn genesis_hash_is_pinned_for_default_config() {
+        let genesis = Block::genesis(&NodeConfig::default());
 
-    pub fn new(
-        index: u64,
-        previous_hash: Hash,
-        timestamp: Timestamp,
-        transactions: Vec<Transaction>,
-    ) -> Self {
-        let _ = (index, previous_hash, timestamp, transactions);
-        todo!("block constructor implementation is not added yet")
+        assert_eq!(genesis.hash().to_string(), DEFAULT_GENESIS_HASH);
     }
 
-    pub fn calculate_hash(&self) -> Hash {
-        let _ = self;
-        todo!("block hash calculation implementation is not added yet")
+    #[test]
+    fn genesis_is_deterministic() {
+        let config = NodeConfig::default();
+
+        assert_eq!(
+            Block::genesis(&config).hash(),
+            Block::genesis(&config.clone()).hash()
+        );
+    }
+
+    #[test]
+    fn genesis_hash_depends_on_timestamp() {
+        let config = NodeConfig::default();
+        let shifted = NodeConfig {
+            genesis_timestamp: config.genesis_timestamp + 1,
+            ..config.clone()
+        };
+
+        assert_ne!(
+            Block::genesis(&config).hash(),
+            Block::genesis(&shifted).hash()
+        );
+    }
+
+    #[test]
+    fn genesis_hash_depends_on_alloc() {
+        let config = NodeConfig::default();
+        let funded = NodeConfig {
+            genesis_alloc: vec![(Address::from_bytes([1_u8; 20]), 1_000)],
+            ..config.clone()
+        };
+        let genesis = Block::genesis(&funded);
+
+        assert_ne!(Block::genesis(&config).hash(), genesis.hash());
+        assert_eq!(
+            genesis.header.state_root,
+            WorldState::genesis(&funded).state_root()
+        );
+    }
+
+    #[test]
+    fn body_not_matching_transactions_root_is_rejected() {
+        let mut block = Block::genesis(&NodeConfig::default());
+        assert_eq!(block.validate_body(), Ok(()));
+
+        block.header.transactions_root = Hash::keccak(b"other body");
+
+        assert_eq!(
+            block.validate_body(),
+            Err(BlockError::InvalidTransactionsRoot)
+        );
     }
 }
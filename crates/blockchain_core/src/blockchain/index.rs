@@ -0,0 +1,112 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::block::Block;
+use crate::types::{Hash, U256};
+
+#[derive(Debug, Clone)]
+pub struct BlockEntry {
+    pub block: Block,
+    pub chain_work: U256,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BlockIndex {
+    entries: HashMap<Hash, BlockEntry>,
+    best: Option<Hash>,
+}
+
+impl BlockIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, block: Block, chain_work: U256) -> Hash {
+        let hash = block.hash();
+        let is_best = self
+            .best_entry()
+            .is_none_or(|best| chain_work > best.chain_work);
+        self.entries.insert(hash, BlockEntry { block, chain_work });
+        if is_best {
+            self.best = Some(hash);
+        }
+        hash
+    }
+
+    pub fn get(&self, hash: &Hash) -> Option<&BlockEntry> {
+        self.entries.get(hash)
+    }
+
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.entries.contains_key(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn best(&self) -> Option<Hash> {
+        self.best
+    }
+
+    pub fn tips(&self) -> Vec<Hash> {
+        let parents: HashSet<Hash> = self
+            .entries
+            .values()
+            .map(|entry| entry.block.header.parent_hash)
+            .collect();
+        self.entries
+            .keys()
+            .filter(|hash| !parents.contains(*hash))
+            .copied()
+            .collect()
+    }
+
+    pub fn ancestor(&self, hash: &Hash, number: u64) -> Option<&Block> {
+        let mut entry = self.entries.get(hash)?;
+        while entry.block.number() > number {
+            entry = self.entries.get(&entry.block.header.parent_hash)?;
+        }
+        (entry.block.number() == number).then_some(&entry.block)
+    }
+
+    pub fn remove_with_descendants(&mut self, hash: &Hash) -> Vec<Block> {
+        let mut removed_hashes = HashSet::from([*hash]);
+        loop {
+            let descendants: Vec<Hash> = self
+                .entries
+                .iter()
+                .filter(|(child, entry)| {
+                    !removed_hashes.contains(*child)
+                        && removed_hashes.contains(&entry.block.header.parent_hash)
+                })
+                .map(|(child, _)| *child)
+                .collect();
+            if descendants.is_empty() {
+                break;
+            }
+            removed_hashes.extend(descendants);
+        }
+
+        let removed = removed_hashes
+            .iter()
+            .filter_map(|hash| self.entries.remove(hash))
+            .map(|entry| entry.block)
+            .collect();
+        if self.best.is_some_and(|best| removed_hashes.contains(&best)) {
+            self.best = self
+                .entries
+                .iter()
+                .max_by_key(|(_, entry)| entry.chain_work)
+                .map(|(hash, _)| *hash);
+        }
+        removed
+    }
+
+    fn best_entry(&self) -> Option<&BlockEntry> {
+        self.best.and_then(|hash| self.entries.get(&hash))
+    }
+}
@@ -1,23 +1,1156 @@
-use crate::block::Block;
-use crate::error::BlockchainError;
+pub mod index;
+pub mod orphan;
+pub mod reorg;
+pub mod template;
+
+pub use index::*;
+pub use orphan::*;
+pub use reorg::*;
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use crate::block::{Block, BlockHeader};
+use crate::config::NodeConfig;
+use crate::consensus::{self, PowConsensus};
+use crate::error::{BlockError, BlockchainError, MempoolError, StorageError};
+use crate::fee;
+use crate::mempool::{ChainStateView, Mempool};
+use crate::state::{BlockUndo, WorldState};
+use crate::storage::StoredBlock;
+use crate::transaction::Transaction;
+use crate::types::{Address, Clock, Hash, SystemClock, Timestamp, U256};
 
 #[derive(Debug, Clone)]
 pub struct Blockchain {
     pub blocks: Vec<Block>,
+    pub index: BlockIndex,
+    pub state: WorldState,
+    undo: HashMap<Hash, BlockUndo>,
+    senders: HashMap<Hash, Vec<Address>>,
+    pub config: NodeConfig,
+    pub consensus: PowConsensus,
+    pub clock: Arc<dyn Clock>,
 }
 
 impl Blockchain {
-    pub fn new() -> Self {
-        todo!("blockchain constructor implementation is not added yet")
+    pub fn new(config: &NodeConfig) -> Result<Self, BlockchainError> {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(config: &NodeConfig, clock: Arc<dyn Clock>) -> Result<Self, BlockchainError> {
+        let consensus = PowConsensus::new(config)?;
+        let genesis = Block::genesis(config);
+        let mut index = BlockIndex::new();
+        index.insert(genesis.clone(), consensus::block_work(genesis.header.bits));
+
+        Ok(Self {
+            blocks: vec![genesis],
+            index,
+            state: WorldState::genesis(config),
+            undo: HashMap::new(),
+            senders: HashMap::new(),
+            config: config.clone(),
+            consensus,
+            clock,
+        })
+    }
+
+    /// Replays blocks from local storage. Stored senders skip signature
+    /// recovery for blocks below the last checkpoint.
+    pub fn restore(config: &NodeConfig, stored: Vec<StoredBlock>) -> Result<Self, BlockchainError> {
+        let mut chain = Self::new(config)?;
+        for entry in stored {
+            let hash = entry.block.hash();
+            chain.senders.insert(hash, entry.senders);
+            chain.add_block(entry.block)?;
+            if chain.undo.get(&hash) != Some(&entry.undo) {
+                return Err(StorageError::Corrupted(format!(
+                    "undo data mismatch for block {hash}"
+                ))
+                .into());
+            }
+        }
+        Ok(chain)
+    }
+
+    pub fn stored_blocks(&self) -> Vec<StoredBlock> {
+        self.blocks
+            .iter()
+            .skip(1)
+            .map(|block| StoredBlock {
+                block: block.clone(),
+                undo: self.undo.get(&block.hash()).cloned().unwrap_or_default(),
+                senders: self.senders.get(&block.hash()).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    pub fn tip(&self) -> &Block {
+        self.blocks
+            .last()
+            .expect("blockchain always contains the genesis block")
+    }
+
+    pub fn height(&self) -> u64 {
+        self.tip().number()
     }
 
-    pub fn add_block(&mut self, block: Block) -> Result<(), BlockchainError> {
-        let _ = (self, block);
-        todo!("add block implementation is not added yet")
+    pub fn chain_work(&self) -> U256 {
+        self.index
+            .get(&self.tip().hash())
+            .map(|entry| entry.chain_work)
+            .unwrap_or_default()
+    }
+
+    pub fn next_base_fee(&self) -> u64 {
+        fee::next_base_fee(&self.tip().header)
+    }
+
+    pub fn next_bits(&self) -> u32 {
+        let parent = self.tip();
+        self.consensus.next_bits(&self.retarget_window(parent))
+    }
+
+    /// Indexes the block and switches the active chain to the heaviest known
+    /// branch; the returned update lists every block that left or joined it.
+    pub fn add_block(&mut self, block: Block) -> Result<ChainUpdate, BlockchainError> {
+        if self.index.contains(&block.hash()) {
+            return Err(BlockError::DuplicateBlock.into());
+        }
+
+        self.validate_checkpoint(&block)?;
+        let parent = self
+            .index
+            .get(&block.header.parent_hash)
+            .ok_or(BlockError::UnknownParent)?;
+        self.validate_link(&parent.block, &block)?;
+        let chain_work = parent.chain_work + consensus::block_work(block.header.bits);
+
+        if block.header.parent_hash != self.tip().hash() {
+            self.index.insert(block, chain_work);
+            return self.activate_best_chain();
+        }
+
+        self.connect_block(block.clone())?;
+        self.index.insert(block.clone(), chain_work);
+        Ok(ChainUpdate {
+            disconnected: Vec::new(),
+            connected: vec![block],
+        })
     }
 
     pub fn validate_chain(&self) -> bool {
-        let _ = self;
-        todo!("chain validation implementation is not added yet")
+        let mut state = WorldState::genesis(&self.config);
+        self.blocks.windows(2).all(|pair| {
+            if self.validate_link(&pair[0], &pair[1]).is_err() {
+                return false;
+            }
+
+            match self.execute_block(&state, &pair[1]) {
+                Ok((next, _, _)) => {
+                    state = next;
+                    true
+                }
+                Err(_) => false,
+            }
+        })
+    }
+
+    pub fn disconnect_block(&mut self) -> Result<Block, BlockchainError> {
+        if self.blocks.len() == 1 {
+            return Err(BlockError::CannotDisconnectGenesis.into());
+        }
+
+        let hash = self.tip().hash();
+        let undo = self.undo.remove(&hash).ok_or(BlockError::MissingUndoData)?;
+        self.state.apply_undo(&undo);
+        self.senders.remove(&hash);
+        Ok(self
+            .blocks
+            .pop()
+            .expect("non-genesis tip is present after length check"))
+    }
+
+    pub fn last_checkpoint(&self) -> Option<u64> {
+        self.config
+            .checkpoints
+            .iter()
+            .map(|(height, _)| *height)
+            .max()
+    }
+
+    /// Highest checkpoint the active chain has already passed; history up to
+    /// it is final and cannot be replaced by another branch.
+    pub fn reached_checkpoint(&self) -> Option<u64> {
+        self.config
+            .checkpoints
+            .iter()
+            .map(|(height, _)| *height)
+            .filter(|height| *height <= self.height())
+            .max()
+    }
+
+    pub fn undo_data(&self, hash: &Hash) -> Option<&BlockUndo> {
+        self.undo.get(hash)
+    }
+
+    fn connect_block(&mut self, block: Block) -> Result<(), BlockchainError> {
+        let (state, undo, senders) = self.execute_block(&self.state, &block)?;
+        self.state = state;
+        self.undo.insert(block.hash(), undo);
+        self.senders.insert(block.hash(), senders);
+        self.blocks.push(block);
+        Ok(())
+    }
+
+    /// Below the last checkpoint, senders recorded with the block are trusted
+    /// instead of recovering every signature again. Only `restore` seeds them
+    /// from local storage; blocks received from peers carry no senders, so
+    /// every signature of a synced block is still recovered here.
+    fn execute_block(
+        &self,
+        state: &WorldState,
+        block: &Block,
+    ) -> Result<(WorldState, BlockUndo, Vec<Address>), BlockchainError> {
+        let (coinbase, transactions) = block
+            .transactions
+            .split_first()
+            .ok_or(BlockError::EmptyTransactions)?;
+        self.validate_coinbase(coinbase, block)?;
+
+        let previous = state;
+        let mut state = state.clone();
+        let mut touched = BTreeSet::from([block.header.miner]);
+        let mut gas_used = 0_u64;
+        let mut tips = 0_u64;
+        let trusted_senders = self
+            .last_checkpoint()
+            .is_some_and(|checkpoint| block.number() < checkpoint)
+            .then(|| self.senders.get(&block.hash()))
+            .flatten()
+            .filter(|senders| senders.len() == transactions.len());
+        let mut senders = Vec::with_capacity(transactions.len());
+        for (index, transaction) in transactions.iter().enumerate() {
+            if transaction.is_coinbase() {
+                return Err(BlockError::InvalidCoinbase.into());
+            }
+
+            let sender = match trusted_senders {
+                Some(trusted) => {
+                    transaction.validate_fields()?;
+                    trusted[index]
+                }
+                None => transaction.validate()?,
+            };
+            senders.push(sender);
+            let outcome =
+                state.apply_transaction(transaction, sender, block.header.base_fee_per_gas)?;
+            touched.insert(outcome.sender);
+            touched.insert(outcome.recipient);
+            gas_used += outcome.gas_used;
+            tips = tips
+                .checked_add(outcome.tip)
+                .ok_or(BlockError::InvalidCoinbaseReward)?;
+        }
+
+        if gas_used != block.header.gas_used {
+            return Err(BlockError::InvalidGasUsed.into());
+        }
+
+        let reward = self
+            .consensus
+            .subsidy(block.number())
+            .checked_add(tips)
+            .ok_or(BlockError::InvalidCoinbaseReward)?;
+        if coinbase.value != reward {
+            return Err(BlockError::InvalidCoinbaseReward.into());
+        }
+        state.credit(&block.header.miner, coinbase.value)?;
+
+        if state.state_root() != block.header.state_root {
+            return Err(BlockError::InvalidStateRoot.into());
+        }
+
+        Ok((state, BlockUndo::capture(previous, &touched), senders))
+    }
+
+    fn validate_coinbase(&self, coinbase: &Transaction, block: &Block) -> Result<(), BlockError> {
+        let expected = Transaction::coinbase(
+            self.config.chain_id,
+            block.number(),
+            block.header.miner,
+            coinbase.value,
+        );
+        if expected.hash != coinbase.hash {
+            return Err(BlockError::InvalidCoinbase);
+        }
+
+        Ok(())
+    }
+
+    fn conflicts_with_checkpoint(&self, block: &Block) -> bool {
+        self.config
+            .checkpoints
+            .iter()
+            .any(|(height, hash)| *height == block.number() && *hash != block.hash())
+    }
+
+    fn validate_checkpoint(&self, block: &Block) -> Result<(), BlockError> {
+        if self.conflicts_with_checkpoint(block) {
+            return Err(BlockError::CheckpointMismatch);
+        }
+
+        if self
+            .reached_checkpoint()
+            .is_some_and(|checkpoint| block.number() <= checkpoint)
+        {
+            return Err(BlockError::ForkBelowCheckpoint);
+        }
+
+        Ok(())
+    }
+
+    /// Headers from the retarget window start up to and including `parent`.
+    fn retarget_window<'a>(&'a self, parent: &'a Block) -> Vec<&'a BlockHeader> {
+        let start = self.consensus.window_start_number(parent.number());
+        let mut window = vec![&parent.header];
+        let mut cursor = &parent.header;
+        while cursor.number > start {
+            cursor = &self
+                .index
+                .get(&cursor.parent_hash)
+                .expect("indexed blocks always link back to genesis")
+                .block
+                .header;
+            window.push(cursor);
+        }
+        window.reverse();
+        window
+    }
+
+    fn median_time_past(&self, parent: &Block) -> Timestamp {
+        let mut timestamps = Vec::with_capacity(self.config.median_time_span);
+        let mut cursor = Some(parent);
+        while let Some(block) = cursor {
+            if timestamps.len() == self.config.median_time_span {
+                break;
+            }
+
+            timestamps.push(block.header.timestamp);
+            cursor = (block.number() > 0)
+                .then(|| self.index.get(&block.header.parent_hash))
+                .flatten()
+                .map(|entry| &entry.block);
+        }
+        consensus::median_time_past(&timestamps)
+    }
+
+    fn validate_timestamp(&self, parent: &Block, block: &Block) -> Result<(), BlockError> {
+        if block.header.timestamp <= self.median_time_past(parent) {
+            return Err(BlockError::TimestampTooOld);
+        }
+
+        if block.header.timestamp > self.clock.now() + self.config.max_future_drift {
+            return Err(BlockError::TimestampTooFarInFuture);
+        }
+
+        Ok(())
+    }
+
+    fn validate_link(&self, parent: &Block, block: &Block) -> Result<(), BlockError> {
+        if block.header.number != parent.header.number + 1 {
+            return Err(BlockError::InvalidIndex);
+        }
+
+        if block.header.parent_hash != parent.hash() {
+            return Err(BlockError::InvalidPreviousHash);
+        }
+
+        self.validate_timestamp(parent, block)?;
+        self.consensus
+            .validate_block(block, &self.retarget_window(parent))?;
+
+        if block.header.base_fee_per_gas != fee::next_base_fee(&parent.header) {
+            return Err(BlockError::InvalidBaseFee);
+        }
+
+        if block.header.gas_limit != self.config.block_gas_limit {
+            return Err(BlockError::InvalidGasLimit);
+        }
+
+        if block.transactions.is_empty() {
+            return Err(BlockError::EmptyTransactions);
+        }
+
+        let total_gas_limit = block
+            .transactions
+            .iter()
+            .try_fold(0_u64, |total, transaction| {
+                total.checked_add(transaction.gas_limit)
+            });
+        if total_gas_limit.is_none_or(|total| total > block.header.gas_limit) {
+            return Err(BlockError::GasLimitExceeded);
+        }
+
+        block.validate_body()
+    }
+}
+
+impl ChainStateView for Blockchain {
+    fn validate_mempool_transaction(
+        &self,
+        transaction: &Transaction,
+        mempool: &Mempool,
+    ) -> Result<(), MempoolError> {
+        if transaction.chain_id != self.config.chain_id {
+            return Err(MempoolError::WrongChainId);
+        }
+
+        if transaction.gas_limit > self.config.block_gas_limit {
+            return Err(MempoolError::GasLimitExceeded);
+        }
+
+        if transaction.max_fee_per_gas < self.next_base_fee() {
+            return Err(MempoolError::FeeBelowBaseFee);
+        }
+
+        let sender = transaction.recover_signer()?;
+        let account = self.state.account(&sender);
+        if transaction.nonce < account.nonce {
+            return Err(MempoolError::NonceTooLow);
+        }
+
+        let pending_cost = mempool
+            .transactions_from(&sender)
+            .filter(|pending| pending.nonce != transaction.nonce)
+            .try_fold(0_u64, |total, pending| {
+                total.checked_add(pending.max_cost()?)
+            });
+        let total_cost = pending_cost
+            .and_then(|pending| pending.checked_add(transaction.max_cost()?))
+            .ok_or(MempoolError::InsufficientBalance)?;
+        if total_cost > account.balance {
+            return Err(MempoolError::InsufficientBalance);
+        }
+
+        Ok(())
+    }
+
+    fn account_nonce(&self, address: &Address) -> u64 {
+        self.state.account(address).nonce
+    }
+
+    fn base_fee(&self) -> u64 {
+        self.next_base_fee()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::SecretKey;
+
+    use super::*;
+    use crate::consensus::CancelToken;
+    use crate::storage::{ChainStorage, FileStorage};
+    use crate::transaction::UnsignedTransaction;
+    use crate::types::FixedClock;
+
+    const FUNDS: u64 = 1_000_000_000_000_000_000;
+    const TIP: u64 = 1_000;
+
+    fn secret_key() -> SecretKey {
+        SecretKey::from_byte_array([7_u8; 32]).unwrap()
+    }
+
+    fn sender() -> Address {
+        Address::from_secret_key(&secret_key())
+    }
+
+    fn test_config() -> NodeConfig {
+        NodeConfig {
+            genesis_alloc: vec![(sender(), FUNDS)],
+            miner_address: Address::from_bytes([9_u8; 20]),
+            mining_threads: 1,
+            ..NodeConfig::default()
+        }
+    }
+
+    fn test_chain(config: &NodeConfig) -> Blockchain {
+        let clock = FixedClock(Timestamp::new(config.genesis_timestamp));
+        Blockchain::with_clock(config, Arc::new(clock)).unwrap()
+    }
+
+    fn advance_clock(chain: &mut Blockchain) {
+        let now = chain.tip().header.timestamp + chain.config.target_block_time;
+        chain.clock = Arc::new(FixedClock(now));
+    }
+
+    fn seal(chain: &Blockchain, mut block: Block) -> Block {
+        chain
+            .consensus
+            .mine_block(&mut block, 1, &CancelToken::new(), chain.clock.as_ref())
+            .unwrap();
+        block
+    }
+
+    fn next_block(chain: &mut Blockchain, mempool: &Mempool) -> Block {
+        advance_clock(chain);
+        let template = chain.block_template(mempool).unwrap();
+        seal(chain, template)
+    }
+
+    fn transfer(nonce: u64, value: u64) -> Transaction {
+        unsigned_transfer(nonce, value).sign(&secret_key())
+    }
+
+    fn unsigned_transfer(nonce: u64, value: u64) -> UnsignedTransaction {
+        UnsignedTransaction {
+            chain_id: 1,
+            nonce,
+            to: Some(Address::from_bytes([2_u8; 20])),
+            value,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2 * crate::config::DEFAULT_INITIAL_BASE_FEE,
+            max_priority_fee_per_gas: TIP,
+            ..UnsignedTransaction::default()
+        }
+    }
+
+    #[test]
+    fn coinbase_must_pay_exactly_subsidy_plus_tips() {
+        let config = test_config();
+        let mut chain = test_chain(&config);
+        let mut mempool = Mempool::new();
+        mempool.add_transaction(transfer(0, 5), &chain).unwrap();
+        advance_clock(&mut chain);
+        let template = chain.block_template(&mempool).unwrap();
+        let reward = template.transactions[0].value;
+        assert_eq!(reward, chain.consensus.subsidy(1) + 21_000 * TIP);
+
+        for paid in [reward - 1, reward + 1] {
+            let mut transactions = template.transactions.clone();
+            transactions[0] = Transaction::coinbase(config.chain_id, 1, config.miner_address, paid);
+            let block = seal(&chain, Block::new(template.header.clone(), transactions));
+
+            let error = chain.clone().add_block(block).unwrap_err();
+
+            assert!(matches!(
+                error,
+                BlockchainError::Block(BlockError::InvalidCoinbaseReward)
+            ));
+        }
+
+        let block = seal(&chain, template);
+        chain.add_block(block).unwrap();
+        assert_eq!(chain.state.account(&config.miner_address).balance, reward);
+    }
+
+    #[test]
+    fn template_rejects_reward_overflow() {
+        let config = NodeConfig {
+            block_subsidy: u64::MAX,
+            ..test_config()
+        };
+        let mut chain = test_chain(&config);
+        let mempool = pool_with(&chain, &[&transfer(0, 5)]);
+        advance_clock(&mut chain);
+
+        let error = chain.block_template(&mempool).unwrap_err();
+
+        assert!(is_block_error(error, BlockError::InvalidCoinbaseReward));
+        assert!(chain.block_template(&Mempool::new()).is_ok());
+    }
+
+    #[test]
+    fn template_reward_halves_at_interval() {
+        let config = NodeConfig {
+            halving_interval: 2,
+            ..test_config()
+        };
+        let mut chain = test_chain(&config);
+        let mempool = Mempool::new();
+
+        let mut rewards = Vec::new();
+        for _ in 0..4 {
+            let block = next_block(&mut chain, &mempool);
+            rewards.push(block.transactions[0].value);
+            chain.add_block(block).unwrap();
+        }
+
+        let subsidy = config.block_subsidy;
+        assert_eq!(rewards, [subsidy, subsidy / 2, subsidy / 2, subsidy / 4]);
+    }
+
+    fn block_at(chain: &Blockchain, timestamp: Timestamp) -> Block {
+        let mut template = chain.block_template(&Mempool::new()).unwrap();
+        template.header.timestamp = timestamp;
+        seal(chain, template)
+    }
+
+    #[test]
+    fn timestamp_must_exceed_median_time_past() {
+        let mut chain = test_chain(&test_config());
+        for _ in 0..3 {
+            extend(&mut chain, &Mempool::new());
+        }
+        // Genesis and three blocks ten seconds apart: the upper middle is +20.
+        let median = Timestamp::new(chain.config.genesis_timestamp + 20);
+        assert_eq!(chain.median_time_past(chain.tip()), median);
+
+        let error = chain
+            .clone()
+            .add_block(block_at(&chain, median))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            BlockchainError::Block(BlockError::TimestampTooOld)
+        ));
+
+        chain.add_block(block_at(&chain, median + 1)).unwrap();
+    }
+
+    #[test]
+    fn timestamp_may_run_ahead_of_clock_by_drift_only() {
+        let mut chain = test_chain(&test_config());
+        let limit = chain.clock.now() + chain.config.max_future_drift;
+
+        let error = chain
+            .clone()
+            .add_block(block_at(&chain, limit + 1))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            BlockchainError::Block(BlockError::TimestampTooFarInFuture)
+        ));
+
+        chain.add_block(block_at(&chain, limit)).unwrap();
+    }
+
+    fn fork_chain() -> Blockchain {
+        let config = NodeConfig {
+            miner_address: Address::from_bytes([8_u8; 20]),
+            ..test_config()
+        };
+        test_chain(&config)
+    }
+
+    fn extend(chain: &mut Blockchain, mempool: &Mempool) -> Block {
+        let block = next_block(chain, mempool);
+        chain.add_block(block.clone()).unwrap();
+        block
+    }
+
+    #[test]
+    fn heavier_side_branch_triggers_reorg() {
+        let mut chain = test_chain(&test_config());
+        let mut mempool = Mempool::new();
+        mempool.add_transaction(transfer(0, 5), &chain).unwrap();
+        let a1 = extend(&mut chain, &mempool);
+
+        let mut fork = fork_chain();
+        let b1 = extend(&mut fork, &Mempool::new());
+        let b2 = extend(&mut fork, &Mempool::new());
+
+        let update = chain.add_block(b1.clone()).unwrap();
+        assert!(update.is_empty());
+        assert_eq!(chain.tip().hash(), a1.hash());
+
+        let update = chain.add_block(b2.clone()).unwrap();
+        assert_eq!(
+            update
+                .disconnected
+                .iter()
+                .map(Block::hash)
+                .collect::<Vec<_>>(),
+            [a1.hash()]
+        );
+        assert_eq!(
+            update.connected.iter().map(Block::hash).collect::<Vec<_>>(),
+            [b1.hash(), b2.hash()]
+        );
+        assert_eq!(chain.tip().hash(), b2.hash());
+        assert_eq!(chain.state, fork.state);
+        assert_eq!(chain.account_nonce(&sender()), 0);
+        assert!(chain.undo_data(&a1.hash()).is_none());
+    }
+
+    #[test]
+    fn invalid_branch_rolls_back_to_previous_chain() {
+        let mut chain = test_chain(&test_config());
+        let a1 = extend(&mut chain, &Mempool::new());
+        let state = chain.state.clone();
+
+        let mut fork = fork_chain();
+        let b1 = extend(&mut fork, &Mempool::new());
+        advance_clock(&mut fork);
+        let mut template = fork.block_template(&Mempool::new()).unwrap();
+        template.header.state_root = Hash::zero();
+        let b2 = seal(&fork, template);
+
+        chain.add_block(b1.clone()).unwrap();
+        let error = chain.add_block(b2.clone()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            BlockchainError::Block(BlockError::InvalidStateRoot)
+        ));
+        assert_eq!(chain.tip().hash(), a1.hash());
+        assert_eq!(chain.state, state);
+        assert!(chain.undo_data(&a1.hash()).is_some());
+        assert!(chain.undo_data(&b1.hash()).is_none());
+        assert!(chain.index.contains(&b1.hash()));
+        assert!(!chain.index.contains(&b2.hash()));
+        assert!(chain.validate_chain());
+    }
+
+    fn pool_with(chain: &Blockchain, transactions: &[&Transaction]) -> Mempool {
+        let mut mempool = Mempool::new();
+        for transaction in transactions {
+            mempool
+                .add_transaction((*transaction).clone(), chain)
+                .unwrap();
+        }
+        mempool
+    }
+
+    #[test]
+    fn connected_block_removes_included_transactions() {
+        let mut chain = test_chain(&test_config());
+        let included = [transfer(0, 5), transfer(1, 5)];
+        let mut mempool = pool_with(&chain, &[&included[0], &included[1]]);
+
+        let block = extend(&mut chain, &mempool);
+        assert_eq!(block.transactions.len(), 3);
+        mempool.on_block_connected(&block, &chain);
+
+        assert!(mempool.is_empty());
+    }
+
+    #[test]
+    fn connected_block_evicts_stale_nonces() {
+        let mut chain = test_chain(&test_config());
+        let pooled = transfer(0, 5);
+        let mut mempool = pool_with(&chain, &[&pooled]);
+
+        let competing = pool_with(&chain, &[&transfer(0, 6)]);
+        let block = extend(&mut chain, &competing);
+        mempool.on_block_connected(&block, &chain);
+
+        assert!(!mempool.contains(&pooled.hash));
+        assert!(mempool.is_empty());
+    }
+
+    #[test]
+    fn connected_block_evicts_senders_that_can_no_longer_pay() {
+        let mut chain = test_chain(&test_config());
+        let max_gas_cost = 21_000 * 2 * crate::config::DEFAULT_INITIAL_BASE_FEE;
+        let pooled = transfer(1, 10);
+        let mut mempool = pool_with(&chain, &[&pooled]);
+
+        let drain = transfer(0, FUNDS - max_gas_cost);
+        let draining = pool_with(&chain, &[&drain]);
+        let block = extend(&mut chain, &draining);
+        assert!(chain.state.account(&sender()).balance < 10 + max_gas_cost);
+        mempool.on_block_connected(&block, &chain);
+
+        assert!(mempool.is_empty());
+    }
+
+    #[test]
+    fn transactions_of_orphaned_block_return_to_pool() {
+        let mut chain = test_chain(&test_config());
+        let orphaned = transfer(0, 5);
+        let mut mempool = pool_with(&chain, &[&orphaned]);
+        let a1 = extend(&mut chain, &mempool);
+        mempool.on_block_connected(&a1, &chain);
+        assert!(mempool.is_empty());
+
+        let mut fork = fork_chain();
+        let b1 = extend(&mut fork, &Mempool::new());
+        let b2 = extend(&mut fork, &Mempool::new());
+        chain.add_block(b1).unwrap();
+        let update = chain.add_block(b2).unwrap();
+        for block in update.disconnected.iter().rev() {
+            mempool.on_block_disconnected(block, &chain);
+        }
+        for block in &update.connected {
+            mempool.on_block_connected(block, &chain);
+        }
+
+        assert_eq!(mempool.len(), 1);
+        assert!(mempool.contains(&orphaned.hash));
+    }
+
+    fn rejection(
+        chain: &Blockchain,
+        mempool: &Mempool,
+        unsigned: UnsignedTransaction,
+    ) -> MempoolError {
+        mempool
+            .clone()
+            .add_transaction(unsigned.sign(&secret_key()), chain)
+            .unwrap_err()
+    }
+
+    #[test]
+    fn mempool_rejects_transactions_the_chain_cannot_include() {
+        let chain = test_chain(&test_config());
+        let mempool = Mempool::new();
+        let base_fee = chain.next_base_fee();
+
+        let cases = [
+            (
+                UnsignedTransaction {
+                    chain_id: 2,
+                    ..unsigned_transfer(0, 5)
+                },
+                MempoolError::WrongChainId,
+            ),
+            (
+                UnsignedTransaction {
+                    gas_limit: chain.config.block_gas_limit + 1,
+                    ..unsigned_transfer(0, 5)
+                },
+                MempoolError::GasLimitExceeded,
+            ),
+            (
+                UnsignedTransaction {
+                    max_fee_per_gas: base_fee - 1,
+                    max_priority_fee_per_gas: 0,
+                    ..unsigned_transfer(0, 5)
+                },
+                MempoolError::FeeBelowBaseFee,
+            ),
+            (
+                unsigned_transfer(0, FUNDS),
+                MempoolError::InsufficientBalance,
+            ),
+        ];
+        for (unsigned, expected) in cases {
+            assert_eq!(rejection(&chain, &mempool, unsigned), expected);
+        }
+
+        let exact = UnsignedTransaction {
+            max_fee_per_gas: base_fee,
+            max_priority_fee_per_gas: 0,
+            ..unsigned_transfer(0, 5)
+        };
+        mempool
+            .clone()
+            .add_transaction(exact.sign(&secret_key()), &chain)
+            .unwrap();
+    }
+
+    #[test]
+    fn mempool_rejects_nonces_already_used_on_chain() {
+        let mut chain = test_chain(&test_config());
+        let included = pool_with(&chain, &[&transfer(0, 5)]);
+        extend(&mut chain, &included);
+        let mempool = Mempool::new();
+
+        assert_eq!(
+            rejection(&chain, &mempool, unsigned_transfer(0, 6)),
+            MempoolError::NonceTooLow
+        );
+        mempool
+            .clone()
+            .add_transaction(transfer(1, 6), &chain)
+            .unwrap();
+    }
+
+    #[test]
+    fn mempool_balance_check_counts_other_pending_transactions() {
+        let chain = test_chain(&test_config());
+        let half = FUNDS / 2;
+        let mut mempool = pool_with(&chain, &[&transfer(0, half)]);
+
+        assert_eq!(
+            rejection(&chain, &mempool, unsigned_transfer(1, half)),
+            MempoolError::InsufficientBalance
+        );
+
+        let replacement = UnsignedTransaction {
+            max_fee_per_gas: 4 * crate::config::DEFAULT_INITIAL_BASE_FEE,
+            max_priority_fee_per_gas: 2 * TIP,
+            ..unsigned_transfer(0, half)
+        };
+        mempool
+            .add_transaction(replacement.sign(&secret_key()), &chain)
+            .unwrap();
+        assert_eq!(mempool.len(), 1);
+    }
+
+    #[test]
+    fn block_over_its_gas_limit_is_rejected() {
+        let config = NodeConfig {
+            block_gas_limit: 50_000,
+            ..test_config()
+        };
+        let mut chain = test_chain(&config);
+        let mempool = pool_with(&chain, &[&transfer(0, 5), &transfer(1, 5)]);
+        advance_clock(&mut chain);
+        let template = chain.block_template(&mempool).unwrap();
+        assert_eq!(template.transactions.len(), 3);
+
+        let mut transactions = template.transactions.clone();
+        transactions.push(transfer(2, 5));
+        let block = seal(&chain, Block::new(template.header.clone(), transactions));
+        let error = chain.clone().add_block(block).unwrap_err();
+
+        assert!(matches!(
+            error,
+            BlockchainError::Block(BlockError::GasLimitExceeded)
+        ));
+        chain.add_block(seal(&chain, template)).unwrap();
+    }
+
+    /// Deterministic xorshift generator, enough to vary block contents.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+    }
+
+    fn random_transaction(
+        rng: &mut Rng,
+        chain: &Blockchain,
+        mempool: &Mempool,
+        keys: &[SecretKey],
+    ) -> Transaction {
+        let key = &keys[rng.below(keys.len() as u64) as usize];
+        let sender = Address::from_secret_key(key);
+        let nonce =
+            chain.account_nonce(&sender) + mempool.transactions_from(&sender).count() as u64;
+        let (to, data) = if rng.below(3) == 0 {
+            (None, vec![0x60, 0x00, rng.below(256) as u8])
+        } else {
+            let recipient = match rng.below(3) {
+                0 => Address::from_secret_key(&keys[0]),
+                _ => Address::from_bytes([rng.below(256) as u8; 20]),
+            };
+            (Some(recipient), Vec::new())
+        };
+
+        UnsignedTransaction {
+            chain_id: 1,
+            nonce,
+            to,
+            value: rng.below(1_000_000),
+            gas_limit: 100_000,
+            max_fee_per_gas: 2 * crate::config::DEFAULT_INITIAL_BASE_FEE,
+            max_priority_fee_per_gas: rng.below(TIP) + 1,
+            data,
+            ..UnsignedTransaction::default()
+        }
+        .sign(key)
+    }
+
+    #[test]
+    fn disconnecting_random_blocks_restores_every_state() {
+        let keys: Vec<SecretKey> = (1..=3)
+            .map(|byte| SecretKey::from_byte_array([byte; 32]).unwrap())
+            .collect();
+        let config = NodeConfig {
+            genesis_alloc: keys
+                .iter()
+                .map(|key| (Address::from_secret_key(key), FUNDS))
+                .collect(),
+            ..test_config()
+        };
+
+        for seed in 1..=8 {
+            let mut rng = Rng(seed);
+            let mut chain = test_chain(&config);
+            let mut history = vec![chain.state.clone()];
+            for _ in 0..6 {
+                let mut mempool = Mempool::new();
+                for _ in 0..rng.below(4) {
+                    let transaction = random_transaction(&mut rng, &chain, &mempool, &keys);
+                    mempool.add_transaction(transaction, &chain).unwrap();
+                }
+                chain.config.miner_address = match rng.below(2) {
+                    0 => config.miner_address,
+                    _ => Address::from_secret_key(&keys[0]),
+                };
+
+                extend(&mut chain, &mempool);
+                assert_eq!(chain.state.state_root(), chain.tip().header.state_root);
+                history.push(chain.state.clone());
+            }
+
+            history.pop();
+            while let Some(state) = history.pop() {
+                chain.disconnect_block().unwrap();
+                assert_eq!(chain.state, state);
+                assert_eq!(chain.state.state_root(), chain.tip().header.state_root);
+            }
+            assert_eq!(chain.height(), 0);
+            assert!(matches!(
+                chain.disconnect_block().unwrap_err(),
+                BlockchainError::Block(BlockError::CannotDisconnectGenesis)
+            ));
+        }
+    }
+
+    #[test]
+    fn stored_chain_restores_through_file_storage() {
+        let config = test_config();
+        let mut chain = test_chain(&config);
+        let mut mempool = Mempool::new();
+        mempool.add_transaction(transfer(0, 5), &chain).unwrap();
+        extend(&mut chain, &mempool);
+        extend(&mut chain, &Mempool::new());
+
+        let path = std::env::temp_dir().join(format!(
+            "blockchain-core-restore-{}.bin",
+            std::process::id()
+        ));
+        let storage = FileStorage::new(path.to_string_lossy());
+        storage.save(&chain.stored_blocks()).unwrap();
+        let stored = storage.load();
+        let _ = std::fs::remove_file(&path);
+        let restored = Blockchain::restore(&config, stored.unwrap()).unwrap();
+
+        assert_eq!(restored.tip().hash(), chain.tip().hash());
+        assert_eq!(restored.state, chain.state);
+        assert_eq!(restored.chain_work(), chain.chain_work());
+        for block in &chain.blocks[1..] {
+            assert_eq!(
+                restored.undo_data(&block.hash()),
+                chain.undo_data(&block.hash())
+            );
+        }
+    }
+
+    fn is_block_error(error: BlockchainError, expected: BlockError) -> bool {
+        matches!(error, BlockchainError::Block(actual) if actual == expected)
+    }
+
+    /// Two blocks where the first carries a transfer, plus the stored form of
+    /// the chain with the first block's recorded sender replaced.
+    fn chain_with_forged_sender() -> (Blockchain, Vec<StoredBlock>) {
+        let mut chain = test_chain(&test_config());
+        let mut mempool = Mempool::new();
+        mempool.add_transaction(transfer(0, 5), &chain).unwrap();
+        extend(&mut chain, &mempool);
+        extend(&mut chain, &Mempool::new());
+
+        let mut stored = chain.stored_blocks();
+        assert_eq!(stored[0].senders, [sender()]);
+        stored[0].senders = vec![Address::from_bytes([3_u8; 20])];
+        (chain, stored)
+    }
+
+    #[test]
+    fn stored_senders_are_trusted_below_checkpoint() {
+        let (chain, forged) = chain_with_forged_sender();
+        let config = NodeConfig {
+            checkpoints: vec![(2, chain.tip().hash())],
+            ..test_config()
+        };
+
+        let restored = Blockchain::restore(&config, chain.stored_blocks()).unwrap();
+        assert_eq!(restored.state, chain.state);
+
+        // The forged sender is charged as-is instead of being recovered, and
+        // it has no funds to pay for the transfer.
+        let error = Blockchain::restore(&config, forged).unwrap_err();
+        assert!(matches!(
+            error,
+            BlockchainError::State(crate::error::StateError::InsufficientBalance)
+        ));
+    }
+
+    #[test]
+    fn stored_senders_are_recovered_without_checkpoint() {
+        let (chain, forged) = chain_with_forged_sender();
+
+        let restored = Blockchain::restore(&test_config(), forged).unwrap();
+
+        assert_eq!(restored.state, chain.state);
+        assert_eq!(restored.stored_blocks()[0].senders, [sender()]);
+    }
+
+    #[test]
+    fn synced_blocks_below_checkpoint_recover_senders() {
+        let (chain, _) = chain_with_forged_sender();
+        let config = NodeConfig {
+            checkpoints: vec![(2, chain.tip().hash())],
+            ..test_config()
+        };
+        let mut synced = test_chain(&config);
+        synced.clock = chain.clock.clone();
+
+        for stored in chain.stored_blocks() {
+            synced.add_block(stored.block).unwrap();
+        }
+
+        assert_eq!(synced.state, chain.state);
+        assert_eq!(synced.stored_blocks()[0].senders, [sender()]);
+    }
+
+    #[test]
+    fn add_block_rejects_checkpoint_conflicts() {
+        let mut chain = test_chain(&test_config());
+        let a1 = extend(&mut chain, &Mempool::new());
+        let a2 = extend(&mut chain, &Mempool::new());
+        let a3 = next_block(&mut chain.clone(), &Mempool::new());
+        let b1 = extend(&mut fork_chain(), &Mempool::new());
+
+        chain.config.checkpoints = vec![(1, a1.hash())];
+        let error = chain.add_block(b1.clone()).unwrap_err();
+        assert!(is_block_error(error, BlockError::CheckpointMismatch));
+
+        chain.config.checkpoints = vec![(2, a2.hash())];
+        let error = chain.add_block(b1.clone()).unwrap_err();
+        assert!(is_block_error(error, BlockError::ForkBelowCheckpoint));
+
+        chain.config.checkpoints = vec![(3, Hash::keccak(b"other block"))];
+        let error = chain.add_block(a3).unwrap_err();
+        assert!(is_block_error(error, BlockError::CheckpointMismatch));
+
+        assert_eq!(chain.tip().hash(), a2.hash());
+        assert!(!chain.index.contains(&b1.hash()));
+    }
+
+    #[test]
+    fn reorganize_rejects_checkpoint_conflicts() {
+        let mut chain = test_chain(&test_config());
+        let a1 = extend(&mut chain, &Mempool::new());
+        let b1 = extend(&mut fork_chain(), &Mempool::new());
+        chain.add_block(b1.clone()).unwrap();
+
+        chain.config.checkpoints = vec![(1, a1.hash())];
+        let error = chain.reorganize(&b1.hash()).unwrap_err();
+        assert!(is_block_error(error, BlockError::CheckpointMismatch));
+
+        let a2 = extend(&mut chain, &Mempool::new());
+        chain.config.checkpoints = vec![(2, a2.hash())];
+        let error = chain.reorganize(&b1.hash()).unwrap_err();
+        assert!(is_block_error(error, BlockError::ForkBelowCheckpoint));
+
+        assert_eq!(chain.tip().hash(), a2.hash());
+        assert!(chain.validate_chain());
+    }
+
+    #[test]
+    fn block_with_wrong_bits_is_rejected() {
+        let mut chain = test_chain(&test_config());
+        advance_clock(&mut chain);
+        let mut template = chain.block_template(&Mempool::new()).unwrap();
+        assert_eq!(template.header.bits, chain.next_bits());
+        template.header.bits = crate::config::DEFAULT_POW_LIMIT_BITS;
+        let block = seal(&chain, template);
+
+        let error = chain.add_block(block).unwrap_err();
+
+        assert!(is_block_error(error, BlockError::InvalidTarget));
+        assert_eq!(chain.height(), 0);
     }
 }
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use crate::block::Block;
+use crate::config::OrphanPoolConfig;
+use crate::types::{Hash, Timestamp};
+
+#[derive(Debug, Clone)]
+pub struct OrphanBlock {
+    pub block: Block,
+    pub received_at: Timestamp,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct OrphanPool {
+    config: OrphanPoolConfig,
+    orphans: HashMap<Hash, OrphanBlock>,
+    by_parent: HashMap<Hash, Vec<Hash>>,
+}
+
+impl OrphanPool {
+    pub fn new(config: OrphanPoolConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn insert(&mut self, block: Block, received_at: Timestamp) -> bool {
+        let hash = block.hash();
+        if self.config.max_blocks == 0 || self.orphans.contains_key(&hash) {
+            return false;
+        }
+
+        while self.orphans.len() >= self.config.max_blocks {
+            self.evict_oldest();
+        }
+
+        self.by_parent
+            .entry(block.header.parent_hash)
+            .or_default()
+            .push(hash);
+        self.orphans
+            .insert(hash, OrphanBlock { block, received_at });
+        true
+    }
+
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.orphans.contains_key(hash)
+    }
+
+    pub fn get(&self, hash: &Hash) -> Option<&OrphanBlock> {
+        self.orphans.get(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.orphans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orphans.is_empty()
+    }
+
+    /// Walks up through pooled orphans and returns the first ancestor hash
+    /// that is not in the pool, i.e. the block that has to be fetched.
+    pub fn missing_ancestor(&self, hash: &Hash) -> Option<Hash> {
+        let mut current = self.orphans.get(hash)?;
+        while let Some(parent) = self.orphans.get(&current.block.header.parent_hash) {
+            current = parent;
+        }
+        Some(current.block.header.parent_hash)
+    }
+
+    pub fn take_children(&mut self, parent: &Hash) -> Vec<Block> {
+        self.by_parent
+            .remove(parent)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|hash| self.orphans.remove(hash))
+            .map(|orphan| orphan.block)
+            .collect()
+    }
+
+    pub fn remove_with_descendants(&mut self, hash: &Hash) -> Vec<Block> {
+        let mut removed = Vec::new();
+        if let Some(orphan) = self.orphans.remove(hash) {
+            self.unlink(hash, &orphan.block.header.parent_hash);
+            removed.push(orphan.block);
+        }
+
+        let mut pending = vec![*hash];
+        while let Some(parent) = pending.pop() {
+            for block in self.take_children(&parent) {
+                pending.push(block.hash());
+                removed.push(block);
+            }
+        }
+        removed
+    }
+
+    pub fn expire(&mut self, now: Timestamp) -> Vec<Block> {
+        let expired: Vec<Hash> = self
+            .orphans
+            .iter()
+            .filter(|(_, orphan)| now.seconds_since(orphan.received_at) > self.config.expiry)
+            .map(|(hash, _)| *hash)
+            .collect();
+        expired
+            .iter()
+            .filter_map(|hash| self.remove(hash))
+            .collect()
+    }
+
+    fn remove(&mut self, hash: &Hash) -> Option<Block> {
+        let orphan = self.orphans.remove(hash)?;
+        self.unlink(hash, &orphan.block.header.parent_hash);
+        Some(orphan.block)
+    }
+
+    fn unlink(&mut self, hash: &Hash, parent: &Hash) {
+        if let Some(children) = self.by_parent.get_mut(parent) {
+            children.retain(|child| child != hash);
+            if children.is_empty() {
+                self.by_parent.remove(parent);
+            }
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        let oldest = self
+            .orphans
+            .iter()
+            .min_by_key(|(_, orphan)| orphan.received_at)
+            .map(|(hash, _)| *hash);
+        if let Some(hash) = oldest {
+            self.remove(&hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    fn block(parent: Hash, number: u64) -> Block {
+        let mut block = Block::genesis(&NodeConfig::default());
+        block.header.parent_hash = parent;
+        block.header.number = number;
+        block
+    }
+
+    fn pool(max_blocks: usize, expiry: u64) -> OrphanPool {
+        OrphanPool::new(OrphanPoolConfig { max_blocks, expiry })
+    }
+
+    #[test]
+    fn expire_drops_orphans_older_than_expiry() {
+        let mut orphans = pool(8, 100);
+        let old = block(Hash::keccak(b"a"), 1);
+        let fresh = block(Hash::keccak(b"b"), 1);
+        orphans.insert(old.clone(), Timestamp::new(1_000));
+        orphans.insert(fresh.clone(), Timestamp::new(1_050));
+
+        assert!(orphans.expire(Timestamp::new(1_100)).is_empty());
+
+        let expired = orphans.expire(Timestamp::new(1_101));
+
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].hash(), old.hash());
+        assert!(orphans.contains(&fresh.hash()));
+        assert!(orphans.take_children(&old.header.parent_hash).is_empty());
+    }
+
+    #[test]
+    fn full_pool_evicts_the_oldest_orphan() {
+        let mut orphans = pool(2, 1_000);
+        let blocks: Vec<Block> = (1..=3)
+            .map(|n| block(Hash::keccak(&[n as u8]), n))
+            .collect();
+        for (received, block) in blocks.iter().enumerate() {
+            assert!(orphans.insert(block.clone(), Timestamp::new(received as u64)));
+        }
+
+        assert_eq!(orphans.len(), 2);
+        assert!(!orphans.contains(&blocks[0].hash()));
+        assert!(orphans.contains(&blocks[1].hash()));
+        assert!(orphans.contains(&blocks[2].hash()));
+
+        assert!(!pool(0, 1_000).insert(blocks[0].clone(), Timestamp::new(0)));
+    }
+
+    #[test]
+    fn descendants_are_tracked_through_the_pool() {
+        let mut orphans = pool(8, 1_000);
+        let missing = Hash::keccak(b"missing");
+        let child = block(missing, 1);
+        let grandchild = block(child.hash(), 2);
+        let mut sibling = block(child.hash(), 2);
+        sibling.header.extra_nonce = 1;
+        for block in [&grandchild, &sibling, &child] {
+            orphans.insert(block.clone(), Timestamp::new(0));
+        }
+
+        assert_eq!(orphans.missing_ancestor(&grandchild.hash()), Some(missing));
+
+        let removed = orphans.remove_with_descendants(&child.hash());
+
+        assert_eq!(removed.len(), 3);
+        assert!(orphans.is_empty());
+    }
+}
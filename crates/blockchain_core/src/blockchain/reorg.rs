@@ -0,0 +1,113 @@
+use crate::block::Block;
+use crate::blockchain::Blockchain;
+use crate::error::{BlockError, BlockchainError};
+use crate::types::Hash;
+
+#[derive(Debug, Default, Clone)]
+pub struct ChainUpdate {
+    pub disconnected: Vec<Block>,
+    pub connected: Vec<Block>,
+}
+
+impl ChainUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.disconnected.is_empty() && self.connected.is_empty()
+    }
+}
+
+impl Blockchain {
+    pub fn best_tip(&self) -> Hash {
+        self.index.best().unwrap_or_else(|| self.tip().hash())
+    }
+
+    pub fn is_on_active_chain(&self, hash: &Hash) -> bool {
+        self.index.get(hash).is_some_and(|entry| {
+            self.blocks
+                .get(entry.block.number() as usize)
+                .is_some_and(|block| block.hash() == *hash)
+        })
+    }
+
+    pub(crate) fn activate_best_chain(&mut self) -> Result<ChainUpdate, BlockchainError> {
+        let best = self.best_tip();
+        let best_work = self
+            .index
+            .get(&best)
+            .map(|entry| entry.chain_work)
+            .unwrap_or_default();
+        if best_work <= self.chain_work() {
+            return Ok(ChainUpdate::default());
+        }
+
+        self.reorganize(&best)
+    }
+
+    pub fn reorganize(&mut self, new_tip: &Hash) -> Result<ChainUpdate, BlockchainError> {
+        let branch = self.branch_to(new_tip)?;
+        let Some(first) = branch.first() else {
+            return Ok(ChainUpdate::default());
+        };
+
+        if branch
+            .iter()
+            .any(|block| self.conflicts_with_checkpoint(block))
+        {
+            return Err(BlockError::CheckpointMismatch.into());
+        }
+
+        let fork_number = first.number() - 1;
+        if self
+            .reached_checkpoint()
+            .is_some_and(|checkpoint| fork_number < checkpoint)
+        {
+            return Err(BlockError::ForkBelowCheckpoint.into());
+        }
+
+        let mut disconnected = Vec::new();
+        while self.height() > fork_number {
+            disconnected.push(self.disconnect_block()?);
+        }
+        disconnected.reverse();
+
+        let mut connected = Vec::with_capacity(branch.len());
+        for block in branch {
+            if let Err(err) = self.connect_block(block.clone()) {
+                self.index.remove_with_descendants(&block.hash());
+                self.restore_branch(connected.len(), &disconnected)?;
+                return Err(err);
+            }
+            connected.push(block);
+        }
+
+        Ok(ChainUpdate {
+            disconnected,
+            connected,
+        })
+    }
+
+    fn restore_branch(
+        &mut self,
+        connected: usize,
+        disconnected: &[Block],
+    ) -> Result<(), BlockchainError> {
+        for _ in 0..connected {
+            self.disconnect_block()?;
+        }
+        for block in disconnected {
+            self.connect_block(block.clone())?;
+        }
+        Ok(())
+    }
+
+    fn branch_to(&self, new_tip: &Hash) -> Result<Vec<Block>, BlockchainError> {
+        let mut branch = Vec::new();
+        let mut cursor = *new_tip;
+        while !self.is_on_active_chain(&cursor) {
+            let entry = self.index.get(&cursor).ok_or(BlockError::UnknownBlock)?;
+            branch.push(entry.block.clone());
+            cursor = entry.block.header.parent_hash;
+        }
+        branch.reverse();
+        Ok(branch)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::block::{Block, BlockHeader};
+use crate::blockchain::Blockchain;
+use crate::error::{BlockError, BlockchainError};
+use crate::mempool::Mempool;
+use crate::transaction::Transaction;
+use crate::types::Hash;
+
+impl Blockchain {
+    pub fn block_template(&self, mempool: &Mempool) -> Result<Block, BlockchainError> {
+        let parent = self.tip();
+        let number = parent.number() + 1;
+        let base_fee = self.next_base_fee();
+        let miner = self.config.miner_address;
+
+        let mut state = self.state.clone();
+        let mut gas_used = 0_u64;
+        let mut tips = 0_u64;
+        let mut transactions = Vec::new();
+        for transaction in mempool.select_for_block(self.config.block_gas_limit, base_fee) {
+            let Ok(sender) = transaction.validate() else {
+                continue;
+            };
+
+            let mut candidate = state.clone();
+            let Ok(outcome) = candidate.apply_transaction(&transaction, sender, base_fee) else {
+                continue;
+            };
+            let Some(next_tips) = tips.checked_add(outcome.tip) else {
+                continue;
+            };
+
+            state = candidate;
+            gas_used += outcome.gas_used;
+            tips = next_tips;
+            transactions.push(transaction);
+        }
+
+        let reward = self
+            .consensus
+            .subsidy(number)
+            .checked_add(tips)
+            .ok_or(BlockError::InvalidCoinbaseReward)?;
+        state.credit(&miner, reward)?;
+        transactions.insert(
+            0,
+            Transaction::coinbase(self.config.chain_id, number, miner, reward),
+        );
+
+        let timestamp = self.clock.now().max(self.median_time_past(parent) + 1);
+        let header = BlockHeader {
+            parent_hash: parent.hash(),
+            number,
+            timestamp,
+            transactions_root: Hash::zero(),
+            state_root: state.state_root(),
+            bits: self.next_bits(),
+            nonce: 0,
+            extra_nonce: 0,
+            miner,
+            gas_used,
+            gas_limit: self.config.block_gas_limit,
+            base_fee_per_gas: base_fee,
+        };
+        Ok(Block::new(header, transactions))
+    }
+}
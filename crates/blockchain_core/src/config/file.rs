@@ -0,0 +1,173 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::config::NodeConfig;
+use crate::error::ConfigError;
+use crate::types::{Address, Hash};
+
+/// TOML overrides on top of `NodeConfig::default()`; absent keys keep the
+/// default value.
+#[derive(Debug, Default, Deserialize)]
+pub struct FileConfig {
+    pub chain_id: Option<u64>,
+    pub initial_bits: Option<u32>,
+    pub target_block_time: Option<u64>,
+    pub genesis_timestamp: Option<u64>,
+    pub block_gas_limit: Option<u64>,
+    pub checkpoints: Option<Vec<(u64, String)>>,
+    pub miner_address: Option<String>,
+    pub block_subsidy: Option<u64>,
+    pub halving_interval: Option<u64>,
+    pub genesis_alloc: Option<Vec<(String, u64)>>,
+    pub price_bump_percent: Option<u64>,
+    pub max_transactions: Option<usize>,
+    pub max_per_sender: Option<usize>,
+}
+
+impl FileConfig {
+    pub fn parse(raw: &str) -> Result<Self, ConfigError> {
+        toml::from_str(raw).map_err(|e| ConfigError::InvalidToml(e.to_string()))
+    }
+
+    pub fn apply(self, config: &mut NodeConfig) -> Result<(), ConfigError> {
+        if let Some(chain_id) = self.chain_id {
+            config.chain_id = chain_id;
+        }
+
+        if let Some(initial_bits) = self.initial_bits {
+            config.initial_bits = initial_bits;
+        }
+
+        if let Some(target_block_time) = self.target_block_time {
+            config.target_block_time = target_block_time;
+        }
+
+        if let Some(genesis_timestamp) = self.genesis_timestamp {
+            config.genesis_timestamp = genesis_timestamp;
+        }
+
+        if let Some(block_gas_limit) = self.block_gas_limit {
+            config.block_gas_limit = block_gas_limit;
+        }
+
+        if let Some(checkpoints) = self.checkpoints {
+            config.checkpoints = checkpoints
+                .into_iter()
+                .map(|(height, hash)| {
+                    Hash::from_hex(&hash)
+                        .map(|hash| (height, hash))
+                        .map_err(|_| ConfigError::InvalidCheckpoint(height))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(miner_address) = self.miner_address {
+            config.miner_address =
+                Address::from_hex(&miner_address).map_err(|_| ConfigError::InvalidMinerAddress)?;
+        }
+
+        if let Some(block_subsidy) = self.block_subsidy {
+            config.block_subsidy = block_subsidy;
+        }
+
+        if let Some(halving_interval) = self.halving_interval {
+            config.halving_interval = halving_interval;
+        }
+
+        if let Some(genesis_alloc) = self.genesis_alloc {
+            config.genesis_alloc = genesis_alloc
+                .into_iter()
+                .map(|(address, balance)| {
+                    Address::from_hex(&address)
+                        .map(|address| (address, balance))
+                        .map_err(|_| ConfigError::InvalidGenesisAlloc(address))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(price_bump_percent) = self.price_bump_percent {
+            config.mempool.price_bump_percent = price_bump_percent;
+        }
+
+        if let Some(max_transactions) = self.max_transactions {
+            config.mempool.max_transactions = max_transactions;
+        }
+
+        if let Some(max_per_sender) = self.max_per_sender {
+            config.mempool.max_per_sender = max_per_sender;
+        }
+
+        Ok(())
+    }
+}
+
+impl NodeConfig {
+    pub fn from_toml(raw: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        FileConfig::parse(raw)?.apply(&mut config)?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let raw = fs::read_to_string(path).map_err(|e| ConfigError::Io(e.to_string()))?;
+        Self::from_toml(&raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_overrides_only_present_keys() {
+        let config = NodeConfig::from_toml(
+            r#"
+                chain_id = 7
+                checkpoints = [[3, "0x0303030303030303030303030303030303030303030303030303030303030303"]]
+                max_per_sender = 2
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.chain_id, 7);
+        assert_eq!(config.checkpoints, [(3, Hash::from_bytes([3; 32]))]);
+        assert_eq!(config.mempool.max_per_sender, 2);
+        assert_eq!(
+            config.mempool.max_transactions,
+            NodeConfig::default().mempool.max_transactions
+        );
+        assert_eq!(config.initial_bits, NodeConfig::default().initial_bits);
+    }
+
+    #[test]
+    fn invalid_values_are_reported() {
+        assert!(matches!(
+            NodeConfig::from_toml("chain_id = \"one\""),
+            Err(ConfigError::InvalidToml(_))
+        ));
+        assert_eq!(
+            NodeConfig::from_toml("miner_address = \"0x12\"").unwrap_err(),
+            ConfigError::InvalidMinerAddress
+        );
+        assert_eq!(
+            NodeConfig::from_toml("checkpoints = [[4, \"0xzz\"]]").unwrap_err(),
+            ConfigError::InvalidCheckpoint(4)
+        );
+        assert_eq!(
+            NodeConfig::from_toml("genesis_alloc = [[\"nope\", 1]]").unwrap_err(),
+            ConfigError::InvalidGenesisAlloc("nope".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_file_is_an_io_error() {
+        let path = std::env::temp_dir().join(format!("missing-config-{}.toml", std::process::id()));
+
+        assert!(matches!(
+            NodeConfig::from_file(path),
+            Err(ConfigError::Io(_))
+        ));
+    }
+}
@@ -1,16 +1,113 @@
+pub mod file;
+
+pub use file::*;
+
+use crate::types::{Address, Hash};
+
+pub const DEFAULT_POW_LIMIT_BITS: u32 = 0x207f_ffff;
+pub const DEFAULT_INITIAL_BITS: u32 = 0x2000_ffff;
+pub const DEFAULT_TARGET_BLOCK_TIME: u64 = 10;
+pub const DEFAULT_RETARGET_WINDOW: u64 = 20;
+pub const DEFAULT_MEDIAN_TIME_SPAN: usize = 11;
+pub const DEFAULT_MAX_FUTURE_DRIFT: u64 = 120;
+pub const DEFAULT_MINING_NONCE_RANGE: u64 = 1 << 24;
+pub const DEFAULT_BLOCK_SUBSIDY: u64 = 40_000_000_000_000;
+pub const DEFAULT_HALVING_INTERVAL: u64 = 210_000;
+pub const DEFAULT_GENESIS_TIMESTAMP: u64 = 1_735_689_600;
+pub const DEFAULT_INITIAL_BASE_FEE: u64 = 1_000_000_000;
+pub const DEFAULT_BLOCK_GAS_LIMIT: u64 = 30_000_000;
+pub const DEFAULT_PRICE_BUMP_PERCENT: u64 = 10;
+pub const DEFAULT_MEMPOOL_MAX_TRANSACTIONS: usize = 4_096;
+pub const DEFAULT_MEMPOOL_MAX_PER_SENDER: usize = 64;
+pub const DEFAULT_MAX_ORPHAN_BLOCKS: usize = 256;
+pub const DEFAULT_ORPHAN_EXPIRY: u64 = 20 * 60;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MempoolConfig {
+    pub price_bump_percent: u64,
+    pub max_transactions: usize,
+    pub max_per_sender: usize,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            price_bump_percent: DEFAULT_PRICE_BUMP_PERCENT,
+            max_transactions: DEFAULT_MEMPOOL_MAX_TRANSACTIONS,
+            max_per_sender: DEFAULT_MEMPOOL_MAX_PER_SENDER,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OrphanPoolConfig {
+    pub max_blocks: usize,
+    pub expiry: u64,
+}
+
+impl Default for OrphanPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_blocks: DEFAULT_MAX_ORPHAN_BLOCKS,
+            expiry: DEFAULT_ORPHAN_EXPIRY,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NodeConfig {
-    pub difficulty: usize,
+    pub chain_id: u64,
+    pub pow_limit_bits: u32,
+    pub initial_bits: u32,
+    pub target_block_time: u64,
+    pub retarget_window: u64,
+    pub median_time_span: usize,
+    pub max_future_drift: u64,
+    pub genesis_timestamp: u64,
+    pub genesis_alloc: Vec<(Address, u64)>,
+    pub initial_base_fee: u64,
+    pub block_gas_limit: u64,
+    pub mempool: MempoolConfig,
+    pub orphan_pool: OrphanPoolConfig,
+    pub mining_threads: usize,
+    pub mining_nonce_range: u64,
+    pub miner_address: Address,
+    pub block_subsidy: u64,
+    pub halving_interval: u64,
+    pub checkpoints: Vec<(u64, Hash)>,
 }
 
 impl NodeConfig {
-    pub fn new(difficulty: usize) -> Self {
-        Self { difficulty }
+    pub fn new(initial_bits: u32) -> Self {
+        Self {
+            initial_bits,
+            ..Self::default()
+        }
     }
 }
 
 impl Default for NodeConfig {
     fn default() -> Self {
-        Self { difficulty: 2 }
+        Self {
+            chain_id: 1,
+            pow_limit_bits: DEFAULT_POW_LIMIT_BITS,
+            initial_bits: DEFAULT_INITIAL_BITS,
+            target_block_time: DEFAULT_TARGET_BLOCK_TIME,
+            retarget_window: DEFAULT_RETARGET_WINDOW,
+            median_time_span: DEFAULT_MEDIAN_TIME_SPAN,
+            max_future_drift: DEFAULT_MAX_FUTURE_DRIFT,
+            genesis_timestamp: DEFAULT_GENESIS_TIMESTAMP,
+            genesis_alloc: Vec::new(),
+            initial_base_fee: DEFAULT_INITIAL_BASE_FEE,
+            block_gas_limit: DEFAULT_BLOCK_GAS_LIMIT,
+            mempool: MempoolConfig::default(),
+            orphan_pool: OrphanPoolConfig::default(),
+            mining_threads: std::thread::available_parallelism().map_or(1, usize::from),
+            mining_nonce_range: DEFAULT_MINING_NONCE_RANGE,
+            miner_address: Address::zero(),
+            block_subsidy: DEFAULT_BLOCK_SUBSIDY,
+            halving_interval: DEFAULT_HALVING_INTERVAL,
+            checkpoints: Vec::new(),
+        }
     }
 }
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::block::BlockHeader;
+use crate::consensus::target::hash_meets_target;
+use crate::types::U256;
+
+const CANCEL_CHECK_INTERVAL: u64 = 1_024;
+
+#[derive(Debug, Default, Clone)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningStatus {
+    Found(u64),
+    Cancelled,
+    Exhausted,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MiningReport {
+    pub status: MiningStatus,
+    pub hashes: u64,
+    pub elapsed: Duration,
+    pub templates: u64,
+}
+
+impl MiningReport {
+    pub fn hashrate(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            return self.hashes as f64;
+        }
+        self.hashes as f64 / seconds
+    }
+}
+
+pub fn mine_header(
+    header: &BlockHeader,
+    target: &U256,
+    threads: usize,
+    nonce_range: u64,
+    cancel: &CancelToken,
+) -> MiningReport {
+    let started = Instant::now();
+    let search = NonceSearch {
+        target,
+        step: threads.max(1) as u64,
+        last_nonce: nonce_range,
+        cancel,
+        found: AtomicBool::new(false),
+        hashes: AtomicU64::new(0),
+    };
+
+    let status = thread::scope(|scope| {
+        let workers: Vec<_> = (0..search.step)
+            .map(|offset| {
+                let search = &search;
+                let mut header = header.clone();
+                scope.spawn(move || search.run(&mut header, offset))
+            })
+            .collect();
+
+        let mut status = MiningStatus::Exhausted;
+        for worker in workers {
+            match worker.join().expect("mining thread panicked") {
+                MiningStatus::Found(nonce) => status = MiningStatus::Found(nonce),
+                MiningStatus::Cancelled if status == MiningStatus::Exhausted => {
+                    status = MiningStatus::Cancelled;
+                }
+                _ => {}
+            }
+        }
+        status
+    });
+
+    MiningReport {
+        status,
+        hashes: search.hashes.into_inner(),
+        elapsed: started.elapsed(),
+        templates: 1,
+    }
+}
+
+struct NonceSearch<'a> {
+    target: &'a U256,
+    step: u64,
+    last_nonce: u64,
+    cancel: &'a CancelToken,
+    found: AtomicBool,
+    hashes: AtomicU64,
+}
+
+impl NonceSearch<'_> {
+    fn run(&self, header: &mut BlockHeader, start: u64) -> MiningStatus {
+        let mut nonce = start;
+        let mut local_hashes = 0_u64;
+        let status = loop {
+            if nonce > self.last_nonce {
+                break MiningStatus::Exhausted;
+            }
+
+            header.nonce = nonce;
+            local_hashes += 1;
+            if hash_meets_target(&header.hash(), self.target) {
+                if self.found.swap(true, Ordering::Relaxed) {
+                    break MiningStatus::Cancelled;
+                }
+                break MiningStatus::Found(nonce);
+            }
+
+            if local_hashes.is_multiple_of(CANCEL_CHECK_INTERVAL)
+                && (self.cancel.is_cancelled() || self.found.load(Ordering::Relaxed))
+            {
+                break MiningStatus::Cancelled;
+            }
+
+            let Some(next) = nonce.checked_add(self.step) else {
+                break MiningStatus::Exhausted;
+            };
+            nonce = next;
+        };
+
+        self.hashes.fetch_add(local_hashes, Ordering::Relaxed);
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+    use crate::config::NodeConfig;
+    use crate::consensus::target::compact_to_target;
+
+    fn header() -> BlockHeader {
+        Block::genesis(&NodeConfig::default()).header
+    }
+
+    #[test]
+    fn parallel_search_finds_a_valid_nonce() {
+        let header = header();
+        let target = compact_to_target(0x2003_ffff).unwrap();
+
+        let report = mine_header(&header, &target, 4, u64::MAX, &CancelToken::new());
+
+        let MiningStatus::Found(nonce) = report.status else {
+            panic!("nonce not found: {:?}", report.status);
+        };
+        let mut mined = header.clone();
+        mined.nonce = nonce;
+        assert!(hash_meets_target(&mined.hash(), &target));
+        assert!(report.hashes > 0);
+    }
+
+    #[test]
+    fn cancel_from_another_thread_stops_a_hard_search() {
+        let header = header();
+        let target = compact_to_target(0x0300_0001).unwrap();
+        let cancel = CancelToken::new();
+
+        let report = thread::scope(|scope| {
+            let canceller = cancel.clone();
+            scope.spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                canceller.cancel();
+            });
+            mine_header(&header, &target, 4, u64::MAX, &cancel)
+        });
+
+        assert_eq!(report.status, MiningStatus::Cancelled);
+        assert!(report.hashes > 0);
+    }
+}
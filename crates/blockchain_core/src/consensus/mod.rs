@@ -1,23 +1,250 @@
-use crate::block::Block;
+pub mod miner;
+pub mod retarget;
+pub mod reward;
+pub mod target;
+pub mod time;
+
+pub use miner::*;
+pub use retarget::*;
+pub use reward::*;
+pub use target::*;
+pub use time::*;
+
+use std::time::Duration;
+
+use crate::block::{Block, BlockHeader};
+use crate::config::NodeConfig;
+use crate::error::BlockError;
+use crate::types::{Clock, U256};
 
 #[derive(Debug, Clone)]
 pub struct PowConsensus {
-    pub difficulty: usize,
+    pub pow_limit: U256,
+    pub target_block_time: u64,
+    pub retarget_window: u64,
+    pub nonce_range: u64,
+    pub block_subsidy: u64,
+    pub halving_interval: u64,
 }
 
 impl PowConsensus {
-    pub fn new(difficulty: usize) -> Self {
-        let _ = difficulty;
-        todo!("consensus constructor implementation is not added yet")
+    pub fn new(config: &NodeConfig) -> Result<Self, BlockError> {
+        let pow_limit = compact_to_target(config.pow_limit_bits)
+            .filter(|limit| !limit.is_zero())
+            .ok_or(BlockError::InvalidPowLimit)?;
+
+        Ok(Self {
+            pow_limit,
+            target_block_time: config.target_block_time,
+            retarget_window: config.retarget_window,
+            nonce_range: config.mining_nonce_range,
+            block_subsidy: config.block_subsidy,
+            halving_interval: config.halving_interval,
+        })
+    }
+
+    pub fn mine_block(
+        &self,
+        block: &mut Block,
+        threads: usize,
+        cancel: &CancelToken,
+        clock: &dyn Clock,
+    ) -> Option<MiningReport> {
+        let target = self.block_target(block)?;
+        let mut total = MiningReport {
+            status: MiningStatus::Exhausted,
+            hashes: 0,
+            elapsed: Duration::ZERO,
+            templates: 0,
+        };
+
+        loop {
+            let report = mine_header(&block.header, &target, threads, self.nonce_range, cancel);
+            total.status = report.status;
+            total.hashes += report.hashes;
+            total.elapsed += report.elapsed;
+            total.templates += 1;
+
+            match report.status {
+                MiningStatus::Found(nonce) => {
+                    block.header.nonce = nonce;
+                    return Some(total);
+                }
+                MiningStatus::Cancelled => return Some(total),
+                MiningStatus::Exhausted => {
+                    if !Self::roll_template(&mut block.header, clock) {
+                        return Some(total);
+                    }
+                }
+            }
+        }
+    }
+
+    fn roll_template(header: &mut BlockHeader, clock: &dyn Clock) -> bool {
+        let Some(extra_nonce) = header.extra_nonce.checked_add(1) else {
+            return false;
+        };
+
+        header.extra_nonce = extra_nonce;
+        header.nonce = 0;
+        header.timestamp = header.timestamp.max(clock.now());
+        true
+    }
+
+    pub fn validate_block(&self, block: &Block, window: &[&BlockHeader]) -> Result<(), BlockError> {
+        if block.header.bits != self.next_bits(window) {
+            return Err(BlockError::InvalidTarget);
+        }
+
+        self.check_proof_of_work(&block.header)
+    }
+
+    /// Context-free check that the header's own bits are an acceptable target
+    /// and that its hash meets it.
+    pub fn check_proof_of_work(&self, header: &BlockHeader) -> Result<(), BlockError> {
+        let target = self
+            .header_target(header)
+            .ok_or(BlockError::InvalidTarget)?;
+        if !hash_meets_target(&header.hash(), &target) {
+            return Err(BlockError::InsufficientWork);
+        }
+
+        Ok(())
+    }
+
+    pub fn next_bits(&self, window: &[&BlockHeader]) -> u32 {
+        retarget(window, self.target_block_time, &self.pow_limit)
+    }
+
+    pub fn subsidy(&self, height: u64) -> u64 {
+        block_subsidy(self.block_subsidy, self.halving_interval, height)
+    }
+
+    pub fn window_start_number(&self, parent_number: u64) -> u64 {
+        parent_number.saturating_sub(self.retarget_window)
+    }
+
+    pub fn block_target(&self, block: &Block) -> Option<U256> {
+        self.header_target(&block.header)
+    }
+
+    fn header_target(&self, header: &BlockHeader) -> Option<U256> {
+        compact_to_target(header.bits)
+            .filter(|target| !target.is_zero() && *target <= self.pow_limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FixedClock, Timestamp};
+
+    const HARD_BITS: u32 = 0x2003_ffff;
+
+    fn small_range_consensus() -> PowConsensus {
+        let config = NodeConfig {
+            mining_nonce_range: 4,
+            ..NodeConfig::default()
+        };
+        PowConsensus::new(&config).unwrap()
+    }
+
+    fn template(timestamp: u64) -> Block {
+        let mut block = Block::genesis(&NodeConfig::default());
+        block.header.bits = HARD_BITS;
+        block.header.timestamp = Timestamp::new(timestamp);
+        block
     }
 
-    pub fn mine_block(&self, block: &mut Block) {
-        let _ = (self, block);
-        todo!("pow mining implementation is not added yet")
+    fn mine(consensus: &PowConsensus, block: &mut Block, clock: &FixedClock) -> MiningReport {
+        consensus
+            .mine_block(block, 1, &CancelToken::new(), clock)
+            .unwrap()
     }
 
-    pub fn validate_block(&self, block: &Block) -> bool {
-        let _ = (self, block);
-        todo!("block validation implementation is not added yet")
+    #[test]
+    fn invalid_pow_limit_is_rejected() {
+        for pow_limit_bits in [0x0492_3456, 0x2300_0001, 0] {
+            let config = NodeConfig {
+                pow_limit_bits,
+                ..NodeConfig::default()
+            };
+
+            assert_eq!(
+                PowConsensus::new(&config).unwrap_err(),
+                BlockError::InvalidPowLimit
+            );
+        }
+    }
+
+    #[test]
+    fn default_pow_limit_is_accepted() {
+        let consensus = PowConsensus::new(&NodeConfig::default()).unwrap();
+
+        assert_eq!(
+            target_to_compact(&consensus.pow_limit),
+            crate::config::DEFAULT_POW_LIMIT_BITS
+        );
+    }
+
+    #[test]
+    fn small_nonce_range_rolls_templates_until_found() {
+        let consensus = small_range_consensus();
+        let mut block = template(1_000);
+
+        let report = mine(&consensus, &mut block, &FixedClock(Timestamp::new(1_000)));
+
+        assert!(matches!(report.status, MiningStatus::Found(_)));
+        assert!(report.templates > 1);
+        assert_eq!(block.header.extra_nonce, report.templates - 1);
+        assert!(block.header.nonce <= consensus.nonce_range);
+        assert!(hash_meets_target(
+            &block.hash(),
+            &consensus.block_target(&block).unwrap()
+        ));
+    }
+
+    #[test]
+    fn rolled_template_never_moves_timestamp_backwards() {
+        let consensus = small_range_consensus();
+        let mut block = template(1_000);
+
+        mine(&consensus, &mut block, &FixedClock(Timestamp::new(400)));
+
+        assert!(block.header.extra_nonce > 0);
+        assert_eq!(block.header.timestamp, Timestamp::new(1_000));
+    }
+
+    #[test]
+    fn rolled_template_advances_timestamp_to_clock() {
+        let consensus = small_range_consensus();
+        let mut block = template(1_000);
+
+        mine(&consensus, &mut block, &FixedClock(Timestamp::new(1_500)));
+
+        assert!(block.header.extra_nonce > 0);
+        assert_eq!(block.header.timestamp, Timestamp::new(1_500));
+    }
+
+    #[test]
+    fn proof_of_work_is_checked_against_own_bits() {
+        let consensus = small_range_consensus();
+        let mut block = template(1_000);
+        mine(&consensus, &mut block, &FixedClock(Timestamp::new(1_000)));
+        assert_eq!(consensus.check_proof_of_work(&block.header), Ok(()));
+
+        block.header.bits = 0x1d00_ffff;
+        assert_eq!(
+            consensus.check_proof_of_work(&block.header),
+            Err(BlockError::InsufficientWork)
+        );
+
+        for bits in [0x2100_ffff, 0x0180_0000, 0] {
+            block.header.bits = bits;
+            assert_eq!(
+                consensus.check_proof_of_work(&block.header),
+                Err(BlockError::InvalidTarget)
+            );
+        }
     }
 }
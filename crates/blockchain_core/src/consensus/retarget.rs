@@ -0,0 +1,192 @@
+use crate::block::BlockHeader;
+use crate::consensus::target::{compact_to_target, target_to_compact};
+use crate::types::U256;
+
+pub const MAX_ADJUSTMENT_FACTOR: u64 = 4;
+
+/// Next compact target from the retarget window, ordered from the window
+/// start up to the parent. The average target of the blocks mined inside
+/// the window is scaled by actual over expected timespan, so adjustments
+/// already made within the window are not applied a second time.
+pub fn retarget(window: &[&BlockHeader], target_block_time: u64, pow_limit: &U256) -> u32 {
+    let (Some(window_start), Some(parent)) = (window.first(), window.last()) else {
+        return target_to_compact(pow_limit);
+    };
+    let intervals = (window.len() - 1) as u64;
+    if intervals == 0 || target_block_time == 0 {
+        return parent.bits;
+    }
+
+    let Some(average_target) = average_target(&window[1..]) else {
+        return parent.bits;
+    };
+
+    let expected_timespan = intervals * target_block_time;
+    let actual_timespan = parent
+        .timestamp
+        .seconds_since(window_start.timestamp)
+        .clamp(
+            expected_timespan.div_ceil(MAX_ADJUSTMENT_FACTOR),
+            expected_timespan * MAX_ADJUSTMENT_FACTOR,
+        )
+        .max(1);
+
+    let actual_timespan = U256::from(actual_timespan);
+    let expected_timespan = U256::from(expected_timespan);
+    let scaled = match average_target.checked_mul(actual_timespan) {
+        Some(product) => product / expected_timespan,
+        None => (average_target / expected_timespan).saturating_mul(actual_timespan),
+    };
+    target_to_compact(&scaled.clamp(U256::one(), *pow_limit))
+}
+
+/// Exact average without summing the targets, which could overflow.
+fn average_target(headers: &[&BlockHeader]) -> Option<U256> {
+    let count = U256::from(headers.len());
+    let mut quotients = U256::zero();
+    let mut remainders = U256::zero();
+    for header in headers {
+        let target = compact_to_target(header.bits)?;
+        quotients += target / count;
+        remainders += target % count;
+    }
+    Some(quotients + remainders / count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+    use crate::types::Timestamp;
+
+    const BITS: u32 = 0x1f00_ffff;
+    const BLOCK_TIME: u64 = 10;
+    const WINDOW: u64 = 20;
+
+    fn pow_limit() -> U256 {
+        compact_to_target(crate::config::DEFAULT_POW_LIMIT_BITS).unwrap()
+    }
+
+    fn header(number: u64, timestamp: u64, bits: u32) -> BlockHeader {
+        let mut header = crate::block::Block::genesis(&NodeConfig::default()).header;
+        header.number = number;
+        header.timestamp = Timestamp::new(timestamp);
+        header.bits = bits;
+        header
+    }
+
+    /// Window of `WINDOW` intervals at constant bits spanning `timespan`.
+    fn next_bits_for_timespan(timespan: u64) -> u32 {
+        let headers: Vec<BlockHeader> = (0..=WINDOW)
+            .map(|number| header(number, 1_000 + timespan * number / WINDOW, BITS))
+            .collect();
+        let window: Vec<&BlockHeader> = headers.iter().collect();
+        retarget(&window, BLOCK_TIME, &pow_limit())
+    }
+
+    fn scaled_bits(numerator: u64, denominator: u64) -> u32 {
+        let target = compact_to_target(BITS).unwrap();
+        target_to_compact(&(target * U256::from(numerator) / U256::from(denominator)))
+    }
+
+    #[test]
+    fn on_time_window_keeps_target() {
+        assert_eq!(next_bits_for_timespan(WINDOW * BLOCK_TIME), BITS);
+    }
+
+    #[test]
+    fn fast_window_lowers_target() {
+        assert_eq!(
+            next_bits_for_timespan(WINDOW * BLOCK_TIME / 2),
+            scaled_bits(1, 2)
+        );
+    }
+
+    #[test]
+    fn slow_window_raises_target() {
+        assert_eq!(
+            next_bits_for_timespan(WINDOW * BLOCK_TIME * 2),
+            scaled_bits(2, 1)
+        );
+    }
+
+    #[test]
+    fn adjustment_is_clamped_to_factor_four() {
+        assert_eq!(next_bits_for_timespan(1), scaled_bits(1, 4));
+        assert_eq!(
+            next_bits_for_timespan(WINDOW * BLOCK_TIME * 100),
+            scaled_bits(4, 1)
+        );
+    }
+
+    #[test]
+    fn lower_clamp_rounds_up() {
+        let headers = [header(0, 1_000, BITS), header(1, 1_000, BITS)];
+        let window: Vec<&BlockHeader> = headers.iter().collect();
+
+        // Expected timespan 10 clamps to 3 seconds, not 2 (a 5x step).
+        assert_eq!(
+            retarget(&window, BLOCK_TIME, &pow_limit()),
+            scaled_bits(3, 10)
+        );
+    }
+
+    #[test]
+    fn target_never_exceeds_pow_limit() {
+        let limit_bits = crate::config::DEFAULT_POW_LIMIT_BITS;
+        let headers = [
+            header(0, 1_000, limit_bits),
+            header(1, 1_000 + 400, limit_bits),
+        ];
+        let window: Vec<&BlockHeader> = headers.iter().collect();
+
+        assert_eq!(retarget(&window, BLOCK_TIME, &pow_limit()), limit_bits);
+    }
+
+    #[test]
+    fn single_block_window_keeps_parent_bits() {
+        let headers = [header(0, 1_000, BITS)];
+        let window: Vec<&BlockHeader> = headers.iter().collect();
+
+        assert_eq!(retarget(&window, BLOCK_TIME, &pow_limit()), BITS);
+    }
+
+    #[test]
+    fn uses_average_target_of_the_window() {
+        let half_bits = scaled_bits(1, 2);
+        let mut headers = vec![header(0, 1_000, BITS)];
+        headers.push(header(1, 1_000 + BLOCK_TIME, BITS));
+        headers.push(header(2, 1_000 + 2 * BLOCK_TIME, half_bits));
+        let window: Vec<&BlockHeader> = headers.iter().collect();
+
+        assert_eq!(
+            retarget(&window, BLOCK_TIME, &pow_limit()),
+            scaled_bits(3, 4)
+        );
+    }
+
+    /// Constant hashrate with a starting target four times too hard: block
+    /// time is inversely proportional to the target.
+    #[test]
+    fn converges_under_constant_hashrate() {
+        let block_time = 600;
+        let correct = compact_to_target(BITS).unwrap();
+        let mut headers = vec![header(0, 1_000, scaled_bits(1, 4))];
+        for number in 1..=200 {
+            let parent = headers.last().unwrap();
+            let start = headers.len().saturating_sub(WINDOW as usize + 1);
+            let window: Vec<&BlockHeader> = headers[start..].iter().collect();
+            let bits = retarget(&window, block_time, &pow_limit());
+            let target = compact_to_target(bits).unwrap();
+            let solve_time = (correct * U256::from(block_time) / target).as_u64();
+            let timestamp = parent.timestamp.as_u64() + solve_time;
+            headers.push(header(number, timestamp, bits));
+        }
+
+        for header in &headers[100..] {
+            let target = compact_to_target(header.bits).unwrap();
+            assert!(target * U256::from(10) >= correct * U256::from(9));
+            assert!(target * U256::from(10) <= correct * U256::from(11));
+        }
+    }
+}
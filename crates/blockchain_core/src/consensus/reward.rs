@@ -0,0 +1,47 @@
+pub fn block_subsidy(initial_subsidy: u64, halving_interval: u64, height: u64) -> u64 {
+    if halving_interval == 0 {
+        return initial_subsidy;
+    }
+
+    let halvings = height / halving_interval;
+    if halvings >= u64::from(u64::BITS) {
+        return 0;
+    }
+    initial_subsidy >> halvings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUBSIDY: u64 = 40_000_000_000_000;
+    const INTERVAL: u64 = 210_000;
+
+    #[test]
+    fn subsidy_halves_exactly_at_interval_boundaries() {
+        assert_eq!(block_subsidy(SUBSIDY, INTERVAL, 0), SUBSIDY);
+        assert_eq!(block_subsidy(SUBSIDY, INTERVAL, INTERVAL - 1), SUBSIDY);
+        assert_eq!(block_subsidy(SUBSIDY, INTERVAL, INTERVAL), SUBSIDY / 2);
+        assert_eq!(
+            block_subsidy(SUBSIDY, INTERVAL, 2 * INTERVAL - 1),
+            SUBSIDY / 2
+        );
+        assert_eq!(block_subsidy(SUBSIDY, INTERVAL, 2 * INTERVAL), SUBSIDY / 4);
+    }
+
+    #[test]
+    fn subsidy_reaches_zero_after_all_halvings() {
+        assert_eq!(
+            block_subsidy(SUBSIDY, INTERVAL, 63 * INTERVAL),
+            SUBSIDY >> 63
+        );
+        assert_eq!(block_subsidy(SUBSIDY, INTERVAL, 64 * INTERVAL), 0);
+        assert_eq!(block_subsidy(SUBSIDY, 1, u64::MAX), 0);
+        assert_eq!(block_subsidy(1, INTERVAL, INTERVAL), 0);
+    }
+
+    #[test]
+    fn zero_interval_disables_halving() {
+        assert_eq!(block_subsidy(SUBSIDY, 0, u64::MAX), SUBSIDY);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::types::{Hash, U256};
+
+const SIGN_BIT: u32 = 0x0080_0000;
+const MANTISSA_MASK: u32 = 0x007f_ffff;
+
+pub fn compact_to_target(bits: u32) -> Option<U256> {
+    let exponent = bits >> 24;
+    let mut mantissa = bits & MANTISSA_MASK;
+    if exponent <= 3 {
+        mantissa >>= 8 * (3 - exponent);
+    }
+
+    if mantissa != 0 && bits & SIGN_BIT != 0 {
+        return None;
+    }
+
+    if mantissa != 0
+        && (exponent > 34
+            || (mantissa > 0xff && exponent > 33)
+            || (mantissa > 0xffff && exponent > 32))
+    {
+        return None;
+    }
+
+    let target = if exponent <= 3 {
+        U256::from(mantissa)
+    } else {
+        U256::from(mantissa) << (8 * (exponent - 3) as usize)
+    };
+    Some(target)
+}
+
+pub fn target_to_compact(target: &U256) -> u32 {
+    let mut size = target.bits().div_ceil(8) as u32;
+    let mut compact = if size <= 3 {
+        target.low_u32() << (8 * (3 - size))
+    } else {
+        (*target >> (8 * (size - 3) as usize)).low_u32()
+    };
+
+    if compact & SIGN_BIT != 0 {
+        compact >>= 8;
+        size += 1;
+    }
+
+    compact | (size << 24)
+}
+
+pub fn block_work(bits: u32) -> U256 {
+    match compact_to_target(bits) {
+        Some(target) if !target.is_zero() => (!target / (target + 1)) + 1,
+        _ => U256::zero(),
+    }
+}
+
+pub fn hash_meets_target(hash: &Hash, target: &U256) -> bool {
+    U256::from_big_endian(hash.as_bytes()) <= *target
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_bit_is_rejected() {
+        assert_eq!(compact_to_target(0x0492_3456), None);
+        assert_eq!(compact_to_target(0x2080_0001), None);
+    }
+
+    #[test]
+    fn sign_bit_with_zero_mantissa_is_zero() {
+        assert_eq!(compact_to_target(0x0480_0000), Some(U256::zero()));
+        assert_eq!(compact_to_target(0x0180_3456), Some(U256::zero()));
+    }
+
+    #[test]
+    fn small_exponents_truncate_mantissa() {
+        assert_eq!(compact_to_target(0x0012_3456), Some(U256::zero()));
+        assert_eq!(compact_to_target(0x0112_3456), Some(U256::from(0x12)));
+        assert_eq!(compact_to_target(0x0212_3456), Some(U256::from(0x1234)));
+        assert_eq!(compact_to_target(0x0312_3456), Some(U256::from(0x12_3456)));
+        assert_eq!(
+            compact_to_target(0x0412_3456),
+            Some(U256::from(0x1234_5600))
+        );
+    }
+
+    #[test]
+    fn overflow_cutoffs_depend_on_exponent() {
+        assert_eq!(
+            compact_to_target(0x2000_ffff),
+            Some(U256::from(0xffff) << 232)
+        );
+        assert_eq!(
+            compact_to_target(0x2100_ffff),
+            Some(U256::from(0xffff) << 240)
+        );
+        assert_eq!(compact_to_target(0x2101_0000), None);
+        assert_eq!(
+            compact_to_target(0x2200_00ff),
+            Some(U256::from(0xff) << 248)
+        );
+        assert_eq!(compact_to_target(0x2200_0100), None);
+        assert_eq!(compact_to_target(0x2300_0001), None);
+        assert_eq!(compact_to_target(0xff00_0000), Some(U256::zero()));
+    }
+
+    #[test]
+    fn bitcoin_genesis_bits_round_trip() {
+        let target = compact_to_target(0x1d00_ffff).unwrap();
+
+        assert_eq!(target, U256::from(0xffff) << 208);
+        assert_eq!(target_to_compact(&target), 0x1d00_ffff);
+        assert_eq!(block_work(0x1d00_ffff), U256::from(0x1_0001_0001_u64));
+    }
+
+    #[test]
+    fn pow_limit_round_trips() {
+        let bits = crate::config::DEFAULT_POW_LIMIT_BITS;
+        let target = compact_to_target(bits).unwrap();
+
+        assert_eq!(target, U256::from(0x7f_ffff) << 232);
+        assert_eq!(target_to_compact(&target), bits);
+        assert_eq!(block_work(bits), U256::from(2));
+    }
+
+    #[test]
+    fn target_to_compact_normalizes_sign_bit() {
+        assert_eq!(target_to_compact(&U256::zero()), 0);
+        assert_eq!(target_to_compact(&U256::from(0x80)), 0x0200_8000);
+        assert_eq!(target_to_compact(&U256::from(0x12_3456)), 0x0312_3456);
+        assert_eq!(
+            compact_to_target(target_to_compact(&U256::from(0x80))),
+            Some(U256::from(0x80))
+        );
+    }
+
+    #[test]
+    fn invalid_or_zero_targets_have_no_work() {
+        assert_eq!(block_work(0x0492_3456), U256::zero());
+        assert_eq!(block_work(0), U256::zero());
+    }
+
+    #[test]
+    fn hash_target_comparison_is_inclusive() {
+        let hash = Hash::from_bytes([0x11; 32]);
+        let value = U256::from_big_endian(hash.as_bytes());
+
+        assert!(hash_meets_target(&hash, &value));
+        assert!(!hash_meets_target(&hash, &(value - 1)));
+    }
+}
@@ -0,0 +1,39 @@
+use crate::types::Timestamp;
+
+pub fn median_time_past(timestamps: &[Timestamp]) -> Timestamp {
+    let mut sorted = timestamps.to_vec();
+    sorted.sort_unstable();
+    sorted.get(sorted.len() / 2).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamps(values: &[u64]) -> Vec<Timestamp> {
+        values.iter().copied().map(Timestamp::new).collect()
+    }
+
+    #[test]
+    fn odd_window_takes_the_middle_value() {
+        assert_eq!(
+            median_time_past(&timestamps(&[30, 10, 20])),
+            Timestamp::new(20)
+        );
+        assert_eq!(median_time_past(&timestamps(&[7])), Timestamp::new(7));
+    }
+
+    #[test]
+    fn even_window_takes_the_upper_middle_value() {
+        assert_eq!(
+            median_time_past(&timestamps(&[40, 10, 30, 20])),
+            Timestamp::new(30)
+        );
+        assert_eq!(median_time_past(&timestamps(&[10, 10])), Timestamp::new(10));
+    }
+
+    #[test]
+    fn empty_window_is_zero() {
+        assert_eq!(median_time_past(&[]), Timestamp::default());
+    }
+}
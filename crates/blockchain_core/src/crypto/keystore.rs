@@ -0,0 +1,385 @@
+use std::fs;
+
+use aes::Aes128;
+use ctr::Ctr128BE;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use secp256k1::SecretKey;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::error::KeystoreError;
+use crate::types::{Address, Hash};
+
+const KEYSTORE_VERSION: u32 = 3;
+const CIPHER: &str = "aes-128-ctr";
+const KDF_SCRYPT: &str = "scrypt";
+const KDF_PBKDF2: &str = "pbkdf2";
+const PRF_HMAC_SHA256: &str = "hmac-sha256";
+const DERIVED_KEY_LEN: usize = 32;
+const SALT_LEN: usize = 32;
+const IV_LEN: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    Pbkdf2 { iterations: u32 },
+}
+
+impl Default for Kdf {
+    fn default() -> Self {
+        Self::Scrypt {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// Secret key encrypted with a passphrase, stored in the Ethereum keystore v3
+/// JSON layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub id: String,
+    #[serde(default)]
+    pub address: String,
+    #[serde(alias = "Crypto")]
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: KdfParams,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KdfParams {
+    Scrypt {
+        dklen: usize,
+        n: u64,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        c: u32,
+        dklen: usize,
+        prf: String,
+        salt: String,
+    },
+}
+
+impl Keystore {
+    pub fn encrypt(
+        secret_key: &SecretKey,
+        passphrase: &str,
+        kdf: Kdf,
+    ) -> Result<Self, KeystoreError> {
+        let salt = random_bytes::<SALT_LEN>()?;
+        let iv = random_bytes::<IV_LEN>()?;
+        let (kdf_name, kdfparams) = match kdf {
+            Kdf::Scrypt { log_n, r, p } => (
+                KDF_SCRYPT,
+                KdfParams::Scrypt {
+                    dklen: DERIVED_KEY_LEN,
+                    n: 1_u64
+                        .checked_shl(u32::from(log_n))
+                        .ok_or(KeystoreError::InvalidParameters)?,
+                    r,
+                    p,
+                    salt: hex::encode(salt),
+                },
+            ),
+            Kdf::Pbkdf2 { iterations } => (
+                KDF_PBKDF2,
+                KdfParams::Pbkdf2 {
+                    c: iterations,
+                    dklen: DERIVED_KEY_LEN,
+                    prf: PRF_HMAC_SHA256.to_string(),
+                    salt: hex::encode(salt),
+                },
+            ),
+        };
+
+        let derived_key = kdfparams.derive_key(passphrase)?;
+        let mut ciphertext = secret_key.secret_bytes().to_vec();
+        apply_cipher(&derived_key, &iv, &mut ciphertext)?;
+        let mac = calculate_mac(&derived_key, &ciphertext);
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            id: random_uuid()?,
+            address: hex::encode(Address::from_secret_key(secret_key).as_bytes()),
+            crypto: KeystoreCrypto {
+                cipher: CIPHER.to_string(),
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf: kdf_name.to_string(),
+                kdfparams,
+                mac: hex::encode(mac.as_bytes()),
+            },
+        })
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<SecretKey, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::UnsupportedVersion);
+        }
+
+        if self.crypto.cipher != CIPHER {
+            return Err(KeystoreError::UnsupportedCipher);
+        }
+
+        let expected_kdf = match self.crypto.kdfparams {
+            KdfParams::Scrypt { .. } => KDF_SCRYPT,
+            KdfParams::Pbkdf2 { .. } => KDF_PBKDF2,
+        };
+        if self.crypto.kdf != expected_kdf {
+            return Err(KeystoreError::UnsupportedKdf);
+        }
+
+        let derived_key = self.crypto.kdfparams.derive_key(passphrase)?;
+        let mut plaintext = decode_hex(&self.crypto.ciphertext)?;
+        let mac = decode_hex(&self.crypto.mac)?;
+        if calculate_mac(&derived_key, &plaintext)
+            .as_bytes()
+            .as_slice()
+            != mac.as_slice()
+        {
+            return Err(KeystoreError::InvalidPassphrase);
+        }
+
+        let iv = decode_hex(&self.crypto.cipherparams.iv)?;
+        apply_cipher(&derived_key, &iv, &mut plaintext)?;
+        let secret_bytes: [u8; 32] = plaintext
+            .try_into()
+            .map_err(|_| KeystoreError::InvalidSecretKey)?;
+        let secret_key = SecretKey::from_byte_array(secret_bytes)
+            .map_err(|_| KeystoreError::InvalidSecretKey)?;
+
+        if !self.address.is_empty() && self.address()? != Address::from_secret_key(&secret_key) {
+            return Err(KeystoreError::AddressMismatch);
+        }
+
+        Ok(secret_key)
+    }
+
+    pub fn address(&self) -> Result<Address, KeystoreError> {
+        Address::from_hex(&self.address).map_err(|e| KeystoreError::InvalidJson(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        serde_json::from_str(json).map_err(|e| KeystoreError::InvalidJson(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, KeystoreError> {
+        serde_json::to_string_pretty(self).map_err(|e| KeystoreError::InvalidJson(e.to_string()))
+    }
+
+    pub fn load(path: &str) -> Result<Self, KeystoreError> {
+        let json = fs::read_to_string(path).map_err(|e| KeystoreError::Io(e.to_string()))?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), KeystoreError> {
+        fs::write(path, self.to_json()?).map_err(|e| KeystoreError::Io(e.to_string()))
+    }
+}
+
+impl KdfParams {
+    fn derive_key(&self, passphrase: &str) -> Result<Vec<u8>, KeystoreError> {
+        match self {
+            Self::Scrypt {
+                dklen,
+                n,
+                r,
+                p,
+                salt,
+            } => {
+                if *dklen != DERIVED_KEY_LEN || !n.is_power_of_two() || *n < 2 {
+                    return Err(KeystoreError::InvalidParameters);
+                }
+
+                let log_n = n.trailing_zeros() as u8;
+                let params = scrypt::Params::new(log_n, *r, *p, *dklen)
+                    .map_err(|_| KeystoreError::InvalidParameters)?;
+                let mut derived_key = vec![0_u8; *dklen];
+                scrypt::scrypt(
+                    passphrase.as_bytes(),
+                    &decode_hex(salt)?,
+                    &params,
+                    &mut derived_key,
+                )
+                .map_err(|_| KeystoreError::InvalidParameters)?;
+                Ok(derived_key)
+            }
+            Self::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            } => {
+                if prf != PRF_HMAC_SHA256 {
+                    return Err(KeystoreError::UnsupportedKdf);
+                }
+
+                if *dklen != DERIVED_KEY_LEN || *c == 0 {
+                    return Err(KeystoreError::InvalidParameters);
+                }
+
+                let mut derived_key = vec![0_u8; *dklen];
+                pbkdf2::pbkdf2_hmac::<Sha256>(
+                    passphrase.as_bytes(),
+                    &decode_hex(salt)?,
+                    *c,
+                    &mut derived_key,
+                );
+                Ok(derived_key)
+            }
+        }
+    }
+}
+
+fn apply_cipher(derived_key: &[u8], iv: &[u8], data: &mut [u8]) -> Result<(), KeystoreError> {
+    let mut cipher = Ctr128BE::<Aes128>::new_from_slices(&derived_key[..16], iv)
+        .map_err(|_| KeystoreError::InvalidParameters)?;
+    cipher.apply_keystream(data);
+    Ok(())
+}
+
+fn calculate_mac(derived_key: &[u8], ciphertext: &[u8]) -> Hash {
+    let mut payload = Vec::with_capacity(16 + ciphertext.len());
+    payload.extend_from_slice(&derived_key[16..32]);
+    payload.extend_from_slice(ciphertext);
+    Hash::keccak(&payload)
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>, KeystoreError> {
+    let input = input.strip_prefix("0x").unwrap_or(input);
+    hex::decode(input).map_err(|e| KeystoreError::InvalidJson(e.to_string()))
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], KeystoreError> {
+    let mut bytes = [0_u8; N];
+    getrandom::fill(&mut bytes).map_err(|_| KeystoreError::RandomUnavailable)?;
+    Ok(bytes)
+}
+
+fn random_uuid() -> Result<String, KeystoreError> {
+    let mut bytes = random_bytes::<16>()?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PBKDF2_VECTOR: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+    const PBKDF2_VECTOR_SECRET: &str =
+        "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    fn secret_key(byte: u8) -> SecretKey {
+        SecretKey::from_byte_array([byte; 32]).unwrap()
+    }
+
+    #[test]
+    fn published_pbkdf2_vector_decrypts() {
+        let keystore = Keystore::from_json(PBKDF2_VECTOR).unwrap();
+
+        let secret_key = keystore.decrypt("testpassword").unwrap();
+
+        assert_eq!(hex::encode(secret_key.secret_bytes()), PBKDF2_VECTOR_SECRET);
+    }
+
+    #[test]
+    fn published_pbkdf2_vector_rejects_wrong_passphrase() {
+        let keystore = Keystore::from_json(PBKDF2_VECTOR).unwrap();
+
+        assert_eq!(
+            keystore.decrypt("wrongpassword").unwrap_err(),
+            KeystoreError::InvalidPassphrase
+        );
+    }
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let secret_key = secret_key(7);
+        let kdf = Kdf::Scrypt {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        };
+        let keystore = Keystore::encrypt(&secret_key, "passphrase", kdf).unwrap();
+        let restored = Keystore::from_json(&keystore.to_json().unwrap()).unwrap();
+
+        assert_eq!(
+            restored.address().unwrap(),
+            Address::from_secret_key(&secret_key)
+        );
+        assert_eq!(restored.decrypt("passphrase").unwrap(), secret_key);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let keystore =
+            Keystore::encrypt(&secret_key(7), "passphrase", Kdf::Pbkdf2 { iterations: 16 })
+                .unwrap();
+
+        assert_eq!(
+            keystore.decrypt("other").unwrap_err(),
+            KeystoreError::InvalidPassphrase
+        );
+    }
+
+    #[test]
+    fn tampered_address_is_rejected() {
+        let mut keystore =
+            Keystore::encrypt(&secret_key(7), "passphrase", Kdf::Pbkdf2 { iterations: 16 })
+                .unwrap();
+        keystore.address = hex::encode(Address::from_secret_key(&secret_key(8)).as_bytes());
+
+        assert_eq!(
+            keystore.decrypt("passphrase").unwrap_err(),
+            KeystoreError::AddressMismatch
+        );
+    }
+}
@@ -1,3 +1,7 @@
+pub mod keystore;
+
+pub use keystore::*;
+
 pub fn hash_str(input: &str) -> String {
     let _ = input;
     todo!("hashing implementation is not added yet")
@@ -5,12 +5,72 @@ pub enum BlockError {
     #[error("block index is invalid")]
     InvalidIndex,
 
+    #[error("block is already known")]
+    DuplicateBlock,
+
+    #[error("block parent is unknown")]
+    UnknownParent,
+
+    #[error("block is not present in block index")]
+    UnknownBlock,
+
+    #[error("genesis block cannot be disconnected")]
+    CannotDisconnectGenesis,
+
+    #[error("undo data for block is missing")]
+    MissingUndoData,
+
     #[error("block previous hash does not match chain")]
     InvalidPreviousHash,
 
     #[error("block must contain at least one transaction")]
     EmptyTransactions,
 
+    #[error("block coinbase is malformed, misplaced or does not pay the miner")]
+    InvalidCoinbase,
+
+    #[error("block coinbase reward does not match subsidy plus collected tips")]
+    InvalidCoinbaseReward,
+
     #[error("block hash does not match payload")]
     InvalidHash,
+
+    #[error("block timestamp is not after median time past")]
+    TimestampTooOld,
+
+    #[error("block timestamp is too far in the future")]
+    TimestampTooFarInFuture,
+
+    #[error("block target is invalid or does not match expected target")]
+    InvalidTarget,
+
+    #[error("proof-of-work limit is not a valid non-zero target")]
+    InvalidPowLimit,
+
+    #[error("block hash does not meet declared target")]
+    InsufficientWork,
+
+    #[error("block transactions root does not match body")]
+    InvalidTransactionsRoot,
+
+    #[error("block state root does not match executed state")]
+    InvalidStateRoot,
+
+    #[error("block base fee does not match parent gas usage")]
+    InvalidBaseFee,
+
+    #[error("block gas limit does not match configured limit")]
+    InvalidGasLimit,
+
+    #[error("block transactions exceed block gas limit")]
+    GasLimitExceeded,
+
+    #[error("block gas used does not match executed transactions")]
+    InvalidGasUsed,
+
+    #[error("block conflicts with a checkpoint")]
+    CheckpointMismatch,
+
+    #[error("block forks the chain below the last checkpoint")]
+    ForkBelowCheckpoint,
 }
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("config io failed: {0}")]
+    Io(String),
+
+    #[error("config is not valid toml: {0}")]
+    InvalidToml(String),
+
+    #[error("checkpoint at height {0} has an invalid hash")]
+    InvalidCheckpoint(u64),
+
+    #[error("miner address is invalid")]
+    InvalidMinerAddress,
+
+    #[error("genesis allocation address {0} is invalid")]
+    InvalidGenesisAlloc(String),
+}
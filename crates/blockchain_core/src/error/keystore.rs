@@ -0,0 +1,34 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum KeystoreError {
+    #[error("keystore json is malformed: {0}")]
+    InvalidJson(String),
+
+    #[error("keystore version must be 3")]
+    UnsupportedVersion,
+
+    #[error("keystore cipher is not supported")]
+    UnsupportedCipher,
+
+    #[error("keystore key derivation function is not supported")]
+    UnsupportedKdf,
+
+    #[error("keystore parameters are invalid")]
+    InvalidParameters,
+
+    #[error("keystore passphrase is incorrect")]
+    InvalidPassphrase,
+
+    #[error("keystore contains an invalid secret key")]
+    InvalidSecretKey,
+
+    #[error("keystore address does not match secret key")]
+    AddressMismatch,
+
+    #[error("secure random generator failed")]
+    RandomUnavailable,
+
+    #[error("keystore io failed: {0}")]
+    Io(String),
+}
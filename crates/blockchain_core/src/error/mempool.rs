@@ -10,6 +10,27 @@ pub enum MempoolError {
     #[error("transaction already exists in mempool")]
     DuplicateTransaction,
 
-    #[error("transaction failed mempool chain state validation")]
-    ChainStateValidationFailed,
+    #[error("replacement transaction does not bump fees enough")]
+    UnderpricedReplacement,
+
+    #[error("mempool is full and transaction fee is too low to evict others")]
+    PoolFull,
+
+    #[error("sender has too many transactions in mempool")]
+    SenderLimitReached,
+
+    #[error("transaction chain id does not match node chain id")]
+    WrongChainId,
+
+    #[error("transaction nonce is lower than sender account nonce")]
+    NonceTooLow,
+
+    #[error("sender balance cannot cover pending transactions cost")]
+    InsufficientBalance,
+
+    #[error("transaction max fee per gas is below next block base fee")]
+    FeeBelowBaseFee,
+
+    #[error("transaction gas limit exceeds block gas limit")]
+    GasLimitExceeded,
 }
@@ -1,10 +1,18 @@
 pub mod block;
+pub mod config;
+pub mod keystore;
 pub mod mempool;
+pub mod state;
+pub mod storage;
 pub mod transaction;
 pub mod types;
 
 pub use block::*;
+pub use config::*;
+pub use keystore::*;
 pub use mempool::*;
+pub use state::*;
+pub use storage::*;
 pub use transaction::*;
 pub use types::*;
 
@@ -23,4 +31,16 @@ pub enum BlockchainError {
 
     #[error(transparent)]
     Block(#[from] BlockError),
+
+    #[error(transparent)]
+    State(#[from] StateError),
+
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
+
+    #[error(transparent)]
+    Config(#[from] ConfigError),
 }
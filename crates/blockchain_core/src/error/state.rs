@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+use crate::error::TransactionError;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum StateError {
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+
+    #[error("sender balance is too low to cover transaction value")]
+    InsufficientBalance,
+
+    #[error("transaction nonce is lower than sender account nonce")]
+    NonceTooLow,
+
+    #[error("transaction nonce skips ahead of sender account nonce")]
+    NonceGap,
+
+    #[error("transaction max fee per gas is below block base fee")]
+    FeeBelowBaseFee,
+
+    #[error("recipient balance overflows")]
+    BalanceOverflow,
+}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    #[error("storage io failed: {0}")]
+    Io(String),
+
+    #[error("storage data is corrupted: {0}")]
+    Corrupted(String),
+}
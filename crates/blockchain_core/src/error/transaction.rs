@@ -8,6 +8,9 @@ pub enum TransactionError {
     #[error("transaction gas limit must be greater than zero")]
     InvalidGasLimit,
 
+    #[error("transaction gas limit is below intrinsic gas")]
+    IntrinsicGasTooLow,
+
     #[error("max priority fee per gas cannot exceed max fee per gas")]
     PriorityFeeExceedsMaxFee,
 
@@ -16,4 +19,28 @@ pub enum TransactionError {
 
     #[error("transaction signature is invalid")]
     InvalidSignature,
+
+    #[error("transaction signature r or s is out of range")]
+    SignatureOutOfRange,
+
+    #[error("transaction signature s value is not canonical low-s")]
+    HighSignatureS,
+
+    #[error("transaction envelope must start with type 0x02")]
+    InvalidEnvelope,
+
+    #[error("transaction rlp is malformed")]
+    MalformedRlp,
+
+    #[error("transaction rlp is not canonically encoded")]
+    NonCanonicalEncoding,
+
+    #[error("transaction rlp list has wrong number of fields")]
+    InvalidFieldCount,
+
+    #[error("transaction field has invalid length")]
+    InvalidFieldLength,
+
+    #[error("transaction access list is invalid")]
+    InvalidAccessList,
 }
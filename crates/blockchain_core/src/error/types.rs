@@ -23,6 +23,12 @@ pub enum TypeError {
     #[error("signature recovery id must be 0, 1, 27 or 28")]
     InvalidRecoveryId,
 
+    #[error("signature r and s must be within the curve order")]
+    SignatureOutOfRange,
+
+    #[error("signature s value must be in the lower half of the curve order")]
+    HighSignatureS,
+
     #[error("hash cannot be empty")]
     EmptyHash,
 
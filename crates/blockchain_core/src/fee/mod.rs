@@ -0,0 +1,95 @@
+use crate::block::BlockHeader;
+
+pub const ELASTICITY_MULTIPLIER: u64 = 2;
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+pub fn gas_target(gas_limit: u64) -> u64 {
+    gas_limit / ELASTICITY_MULTIPLIER
+}
+
+pub fn next_base_fee(parent: &BlockHeader) -> u64 {
+    let base_fee = parent.base_fee_per_gas;
+    let target = gas_target(parent.gas_limit);
+    if target == 0 || parent.gas_used == target {
+        return base_fee;
+    }
+
+    if parent.gas_used > target {
+        let delta = adjustment(base_fee, parent.gas_used - target, target).max(1);
+        base_fee.saturating_add(delta)
+    } else {
+        let delta = adjustment(base_fee, target - parent.gas_used, target);
+        base_fee.saturating_sub(delta)
+    }
+}
+
+fn adjustment(base_fee: u64, gas_delta: u64, target: u64) -> u64 {
+    let delta = u128::from(base_fee) * u128::from(gas_delta)
+        / u128::from(target)
+        / u128::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+    u64::try_from(delta).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+    use crate::config::NodeConfig;
+
+    fn parent(base_fee: u64, gas_used: u64, gas_limit: u64) -> BlockHeader {
+        let mut header = Block::genesis(&NodeConfig::default()).header;
+        header.base_fee_per_gas = base_fee;
+        header.gas_used = gas_used;
+        header.gas_limit = gas_limit;
+        header
+    }
+
+    #[test]
+    fn base_fee_is_unchanged_at_target() {
+        assert_eq!(
+            next_base_fee(&parent(1_000_000_000, 15_000_000, 30_000_000)),
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn full_block_raises_base_fee_by_one_eighth() {
+        assert_eq!(
+            next_base_fee(&parent(1_000_000_000, 30_000_000, 30_000_000)),
+            1_125_000_000
+        );
+    }
+
+    #[test]
+    fn empty_block_lowers_base_fee_by_one_eighth() {
+        assert_eq!(
+            next_base_fee(&parent(1_000_000_000, 0, 30_000_000)),
+            875_000_000
+        );
+    }
+
+    #[test]
+    fn partial_usage_scales_adjustment() {
+        assert_eq!(
+            next_base_fee(&parent(1_000_000_000, 22_500_000, 30_000_000)),
+            1_062_500_000
+        );
+    }
+
+    #[test]
+    fn usage_above_target_raises_base_fee_by_at_least_one() {
+        assert_eq!(next_base_fee(&parent(7, 15_000_001, 30_000_000)), 8);
+    }
+
+    #[test]
+    fn base_fee_does_not_underflow() {
+        assert_eq!(next_base_fee(&parent(7, 0, 30_000_000)), 7);
+        assert_eq!(next_base_fee(&parent(0, 0, 30_000_000)), 0);
+    }
+
+    #[test]
+    fn zero_gas_limit_keeps_base_fee() {
+        assert_eq!(next_base_fee(&parent(1_000_000_000, 0, 0)), 1_000_000_000);
+        assert_eq!(gas_target(0), 0);
+    }
+}
@@ -4,7 +4,10 @@ pub mod config;
 pub mod consensus;
 pub mod crypto;
 pub mod error;
+pub mod fee;
 pub mod mempool;
+pub mod merkle;
+pub mod state;
 pub mod storage;
 pub mod transaction;
 pub mod types;
@@ -1,26 +1,33 @@
-use std::collections::HashSet;
+pub mod queue;
 
+pub use queue::*;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::block::Block;
+use crate::config::MempoolConfig;
 use crate::error::MempoolError;
 use crate::transaction::Transaction;
-use crate::types::Hash;
+use crate::types::{Address, Hash};
 
 pub trait ChainStateView {
     fn validate_mempool_transaction(
         &self,
         transaction: &Transaction,
         mempool: &Mempool,
-    ) -> Result<(), MempoolError> {
-        let _ = (transaction, mempool);
-        todo!("chain state view implementation is not added yet")
-    }
-}
+    ) -> Result<(), MempoolError>;
 
-impl ChainStateView for () {}
+    fn account_nonce(&self, address: &Address) -> u64;
+
+    fn base_fee(&self) -> u64;
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct Mempool {
-    pub transactions: Vec<Transaction>,
-    hashes: HashSet<Hash>,
+    config: MempoolConfig,
+    queues: HashMap<Address, SenderQueue>,
+    hashes: HashMap<Hash, Address>,
 }
 
 impl Mempool {
@@ -28,37 +35,228 @@ impl Mempool {
         Self::default()
     }
 
+    pub fn with_config(config: MempoolConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
     pub fn add_transaction(
         &mut self,
         transaction: Transaction,
         state: &impl ChainStateView,
     ) -> Result<(), MempoolError> {
-        transaction.validate()?;
+        let sender = transaction.validate()?;
         self.validate_duplicate(&transaction)?;
+        let replaces = self.validate_replacement(&sender, &transaction)?;
         self.validate_against_chain_state(&transaction, state)?;
+        if !replaces {
+            self.ensure_capacity(&sender, &transaction, state.base_fee())?;
+        }
+
+        let base_nonce = state.account_nonce(&sender);
+        let queue = self
+            .queues
+            .entry(sender)
+            .or_insert_with(|| SenderQueue::new(base_nonce));
+        queue.set_base_nonce(base_nonce);
 
-        self.hashes.insert(transaction.hash);
-        self.transactions.push(transaction);
+        self.hashes.insert(transaction.hash, sender);
+        if let Some(replaced) = queue.insert(transaction) {
+            self.hashes.remove(&replaced.hash);
+        }
         Ok(())
     }
 
+    pub fn on_block_connected(&mut self, block: &Block, state: &impl ChainStateView) {
+        for transaction in &block.transactions {
+            if let Some(sender) = self.hashes.get(&transaction.hash).copied() {
+                self.remove(&sender, transaction.nonce);
+            }
+        }
+
+        let transactions = self.drain_all();
+        self.reinject(transactions, state);
+    }
+
+    pub fn on_block_disconnected(&mut self, block: &Block, state: &impl ChainStateView) {
+        let mut transactions = self.drain_all();
+        transactions.extend(
+            block
+                .transactions
+                .iter()
+                .filter(|transaction| !transaction.is_coinbase())
+                .cloned(),
+        );
+        self.reinject(transactions, state);
+    }
+
     pub fn drain_all(&mut self) -> Vec<Transaction> {
         self.hashes.clear();
-        core::mem::take(&mut self.transactions)
+        self.queues
+            .drain()
+            .flat_map(|(_, queue)| queue.iter().cloned().collect::<Vec<_>>())
+            .collect()
     }
 
     pub fn len(&self) -> usize {
-        self.transactions.len()
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.hashes.contains_key(hash)
+    }
+
+    pub fn transactions_from<'a>(
+        &'a self,
+        sender: &Address,
+    ) -> impl Iterator<Item = &'a Transaction> + 'a {
+        self.queues
+            .get(sender)
+            .into_iter()
+            .flat_map(SenderQueue::iter)
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &Transaction> {
+        self.queues.values().flat_map(SenderQueue::pending)
+    }
+
+    pub fn queued(&self) -> impl Iterator<Item = &Transaction> {
+        self.queues.values().flat_map(SenderQueue::queued)
+    }
+
+    pub fn select_for_block(&self, gas_limit: u64, base_fee: u64) -> Vec<Transaction> {
+        let mut heads: HashMap<Address, Vec<&Transaction>> = self
+            .queues
+            .iter()
+            .map(|(sender, queue)| (*sender, queue.pending().collect()))
+            .collect();
+        for pending in heads.values_mut() {
+            pending.reverse();
+        }
+
+        let mut candidates = BinaryHeap::new();
+        for (sender, pending) in &heads {
+            if let Some(transaction) = pending.last() {
+                push_candidate(&mut candidates, *sender, transaction, base_fee);
+            }
+        }
+
+        let mut remaining_gas = gas_limit;
+        let mut selected = Vec::new();
+        while let Some((_, Reverse(sender))) = candidates.pop() {
+            let pending = heads
+                .get_mut(&sender)
+                .expect("candidate sender always has a pending queue");
+            let Some(transaction) = pending.pop() else {
+                continue;
+            };
+
+            if transaction.gas_limit > remaining_gas {
+                continue;
+            }
+
+            remaining_gas -= transaction.gas_limit;
+            selected.push(transaction.clone());
+            if let Some(next) = pending.last() {
+                push_candidate(&mut candidates, sender, next, base_fee);
+            }
+        }
+
+        selected
+    }
+
+    fn reinject(&mut self, mut transactions: Vec<Transaction>, state: &impl ChainStateView) {
+        transactions.sort_by_key(|transaction| transaction.nonce);
+        for transaction in transactions {
+            let _ = self.add_transaction(transaction, state);
+        }
     }
 
     fn validate_duplicate(&self, transaction: &Transaction) -> Result<(), MempoolError> {
-        if self.hashes.contains(&transaction.hash) {
+        if self.hashes.contains_key(&transaction.hash) {
             return Err(MempoolError::DuplicateTransaction);
         }
 
         Ok(())
     }
 
+    fn validate_replacement(
+        &self,
+        sender: &Address,
+        transaction: &Transaction,
+    ) -> Result<bool, MempoolError> {
+        let Some(existing) = self
+            .queues
+            .get(sender)
+            .and_then(|queue| queue.get(transaction.nonce))
+        else {
+            return Ok(false);
+        };
+
+        let bump = self.config.price_bump_percent;
+        if transaction.max_fee_per_gas < bumped_fee(existing.max_fee_per_gas, bump)
+            || transaction.max_priority_fee_per_gas
+                < bumped_fee(existing.max_priority_fee_per_gas, bump)
+        {
+            return Err(MempoolError::UnderpricedReplacement);
+        }
+
+        Ok(true)
+    }
+
+    fn ensure_capacity(
+        &mut self,
+        sender: &Address,
+        transaction: &Transaction,
+        base_fee: u64,
+    ) -> Result<(), MempoolError> {
+        let sender_len = self.queues.get(sender).map_or(0, SenderQueue::len);
+        if sender_len >= self.config.max_per_sender {
+            return Err(MempoolError::SenderLimitReached);
+        }
+
+        if self.len() < self.config.max_transactions {
+            return Ok(());
+        }
+
+        // Only other senders' tails are candidates: evicting from the incoming
+        // sender's own queue would leave the new transaction behind a gap.
+        let tip = |transaction: &Transaction| transaction.effective_tip(base_fee).unwrap_or(0);
+        let cheapest = self
+            .queues
+            .iter()
+            .filter(|(address, _)| *address != sender)
+            .filter_map(|(address, queue)| Some((*address, queue.last()?)))
+            .min_by_key(|(_, tail)| tip(tail))
+            .map(|(address, tail)| (address, tail.nonce, tip(tail)));
+        let Some((evicted_sender, evicted_nonce, evicted_tip)) = cheapest else {
+            return Err(MempoolError::PoolFull);
+        };
+
+        if tip(transaction) <= evicted_tip {
+            return Err(MempoolError::PoolFull);
+        }
+
+        self.remove(&evicted_sender, evicted_nonce);
+        Ok(())
+    }
+
+    fn remove(&mut self, sender: &Address, nonce: u64) -> Option<Transaction> {
+        let queue = self.queues.get_mut(sender)?;
+        let removed = queue.remove(nonce)?;
+        if queue.is_empty() {
+            self.queues.remove(sender);
+        }
+        self.hashes.remove(&removed.hash);
+        Some(removed)
+    }
+
     fn validate_against_chain_state(
         &self,
         transaction: &Transaction,
@@ -67,3 +265,304 @@ impl Mempool {
         state.validate_mempool_transaction(transaction, self)
     }
 }
+
+fn bumped_fee(fee: u64, bump_percent: u64) -> u64 {
+    let bumped = u128::from(fee) * (100 + u128::from(bump_percent)) / 100;
+    u64::try_from(bumped).unwrap_or(u64::MAX)
+}
+
+fn push_candidate(
+    candidates: &mut BinaryHeap<(u64, Reverse<Address>)>,
+    sender: Address,
+    transaction: &Transaction,
+    base_fee: u64,
+) {
+    if let Some(tip) = transaction.effective_tip(base_fee) {
+        candidates.push((tip, Reverse(sender)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::SecretKey;
+
+    use super::*;
+    use crate::blockchain::Blockchain;
+    use crate::config::{DEFAULT_INITIAL_BASE_FEE, NodeConfig};
+    use crate::error::TransactionError;
+    use crate::transaction::UnsignedTransaction;
+
+    const FUNDS: u64 = 1_000_000_000_000_000_000;
+    const MAX_FEE: u64 = 2 * DEFAULT_INITIAL_BASE_FEE;
+
+    fn key(byte: u8) -> SecretKey {
+        SecretKey::from_byte_array([byte; 32]).unwrap()
+    }
+
+    fn address(byte: u8) -> Address {
+        Address::from_secret_key(&key(byte))
+    }
+
+    fn funded_config(senders: &[u8]) -> NodeConfig {
+        NodeConfig {
+            genesis_alloc: senders.iter().map(|byte| (address(*byte), FUNDS)).collect(),
+            ..NodeConfig::default()
+        }
+    }
+
+    fn funded_chain(senders: &[u8]) -> Blockchain {
+        Blockchain::new(&funded_config(senders)).unwrap()
+    }
+
+    fn pool(max_transactions: usize, max_per_sender: usize) -> Mempool {
+        Mempool::with_config(MempoolConfig {
+            max_transactions,
+            max_per_sender,
+            ..MempoolConfig::default()
+        })
+    }
+
+    fn signed(sender: u8, nonce: u64, gas_limit: u64, max_fee: u64, tip: u64) -> Transaction {
+        UnsignedTransaction {
+            chain_id: 1,
+            nonce,
+            to: Some(Address::from_bytes([0xee; 20])),
+            value: 1,
+            gas_limit,
+            max_fee_per_gas: max_fee,
+            max_priority_fee_per_gas: tip,
+            ..UnsignedTransaction::default()
+        }
+        .sign(&key(sender))
+    }
+
+    fn transaction_with_fees(sender: u8, nonce: u64, max_fee: u64, tip: u64) -> Transaction {
+        signed(sender, nonce, 21_000, max_fee, tip)
+    }
+
+    fn transaction_with_gas(sender: u8, nonce: u64, gas_limit: u64, tip: u64) -> Transaction {
+        signed(sender, nonce, gas_limit, MAX_FEE, tip)
+    }
+
+    fn transaction(sender: u8, nonce: u64, tip: u64) -> Transaction {
+        signed(sender, nonce, 21_000, MAX_FEE, tip)
+    }
+
+    fn hashes(transactions: &[Transaction]) -> Vec<Hash> {
+        transactions
+            .iter()
+            .map(|transaction| transaction.hash)
+            .collect()
+    }
+
+    fn add(mempool: &mut Mempool, chain: &Blockchain, transaction: &Transaction) {
+        mempool.add_transaction(transaction.clone(), chain).unwrap();
+    }
+
+    #[test]
+    fn malleated_copy_is_not_accepted_as_second_transaction() {
+        let chain = funded_chain(&[1]);
+        let transaction = transaction(1, 0, 1);
+        let mut malleated = transaction.clone();
+        malleated.signature = transaction.signature.malleated();
+        let malleated = Transaction::from_rlp_bytes(&malleated.to_rlp_bytes()).unwrap();
+
+        let mut mempool = Mempool::new();
+        add(&mut mempool, &chain, &transaction);
+
+        assert_eq!(
+            mempool.add_transaction(malleated.clone(), &chain),
+            Err(MempoolError::Transaction(TransactionError::HighSignatureS))
+        );
+        assert!(!mempool.contains(&malleated.hash));
+        assert_eq!(mempool.len(), 1);
+    }
+
+    #[test]
+    fn replacement_needs_the_configured_fee_bump() {
+        let chain = funded_chain(&[1]);
+        let mut mempool = Mempool::new();
+        let original = transaction_with_fees(1, 0, 2_000_000_000, 1_000);
+        add(&mut mempool, &chain, &original);
+
+        for (max_fee, tip) in [(2_199_999_999, 1_100), (2_200_000_000, 1_099)] {
+            assert_eq!(
+                mempool.add_transaction(transaction_with_fees(1, 0, max_fee, tip), &chain),
+                Err(MempoolError::UnderpricedReplacement)
+            );
+        }
+
+        let replacement = transaction_with_fees(1, 0, 2_200_000_000, 1_100);
+        add(&mut mempool, &chain, &replacement);
+        assert_eq!(mempool.len(), 1);
+        assert!(mempool.contains(&replacement.hash));
+        assert!(!mempool.contains(&original.hash));
+    }
+
+    #[test]
+    fn full_pool_evicts_the_lowest_tip_tail() {
+        let chain = funded_chain(&[1, 2, 3, 4, 5]);
+        let mut mempool = pool(3, 64);
+        let low = transaction(1, 0, 100);
+        let high = transaction(2, 0, 300);
+        let middle = transaction(3, 0, 200);
+        for transaction in [&low, &high, &middle] {
+            add(&mut mempool, &chain, transaction);
+        }
+
+        let incoming = transaction(4, 0, 150);
+        add(&mut mempool, &chain, &incoming);
+        assert!(!mempool.contains(&low.hash));
+
+        assert_eq!(
+            mempool.add_transaction(transaction(5, 0, 150), &chain),
+            Err(MempoolError::PoolFull)
+        );
+
+        let next = transaction(5, 0, 151);
+        add(&mut mempool, &chain, &next);
+        assert!(!mempool.contains(&incoming.hash));
+        assert_eq!(mempool.len(), 3);
+        assert!(mempool.contains(&high.hash) && mempool.contains(&middle.hash));
+    }
+
+    #[test]
+    fn eviction_takes_a_sender_tail_not_its_head() {
+        let chain = funded_chain(&[1, 2, 3]);
+        let mut mempool = pool(3, 64);
+        let head = transaction(1, 0, 500);
+        let tail = transaction(1, 1, 50);
+        add(&mut mempool, &chain, &head);
+        add(&mut mempool, &chain, &tail);
+        add(&mut mempool, &chain, &transaction(2, 0, 100));
+
+        add(&mut mempool, &chain, &transaction(3, 0, 200));
+
+        assert!(mempool.contains(&head.hash));
+        assert!(!mempool.contains(&tail.hash));
+    }
+
+    #[test]
+    fn incoming_sender_never_evicts_its_own_queue() {
+        let chain = funded_chain(&[1, 2]);
+        let mut mempool = pool(3, 64);
+        add(&mut mempool, &chain, &transaction(1, 0, 100));
+        add(&mut mempool, &chain, &transaction(1, 1, 100));
+        let other = transaction(2, 0, 300);
+        add(&mut mempool, &chain, &other);
+
+        add(&mut mempool, &chain, &transaction(1, 2, 1_000));
+
+        assert!(!mempool.contains(&other.hash));
+        assert_eq!(mempool.pending().count(), 3);
+        assert_eq!(mempool.queued().count(), 0);
+
+        let mut mempool = pool(2, 64);
+        add(&mut mempool, &chain, &transaction(1, 0, 100));
+        add(&mut mempool, &chain, &transaction(1, 1, 100));
+        assert_eq!(
+            mempool.add_transaction(transaction(1, 2, 1_000), &chain),
+            Err(MempoolError::PoolFull)
+        );
+        assert_eq!(mempool.pending().count(), 2);
+    }
+
+    #[test]
+    fn sender_limit_applies_to_new_nonces_only() {
+        let chain = funded_chain(&[1]);
+        let mut mempool = pool(64, 2);
+        add(&mut mempool, &chain, &transaction(1, 0, 100));
+        add(&mut mempool, &chain, &transaction(1, 1, 100));
+
+        assert_eq!(
+            mempool.add_transaction(transaction(1, 2, 100), &chain),
+            Err(MempoolError::SenderLimitReached)
+        );
+        add(
+            &mut mempool,
+            &chain,
+            &transaction_with_fees(1, 1, 2 * MAX_FEE, 200),
+        );
+        assert_eq!(mempool.len(), 2);
+    }
+
+    #[test]
+    fn selection_orders_senders_by_tip_and_each_sender_by_nonce() {
+        let chain = funded_chain(&[1, 2]);
+        let mut mempool = Mempool::new();
+        let cheap = [transaction(1, 0, 100), transaction(1, 1, 500)];
+        let rich = [transaction(2, 0, 300), transaction(2, 1, 50)];
+        for transaction in [&cheap[1], &rich[1], &cheap[0], &rich[0]] {
+            add(&mut mempool, &chain, transaction);
+        }
+
+        let selected = mempool.select_for_block(u64::MAX, chain.next_base_fee());
+
+        assert_eq!(
+            hashes(&selected),
+            [rich[0].hash, cheap[0].hash, cheap[1].hash, rich[1].hash]
+        );
+    }
+
+    #[test]
+    fn nonce_gap_keeps_later_transactions_queued() {
+        let chain = funded_chain(&[1]);
+        let mut mempool = Mempool::new();
+        let head = transaction(1, 0, 100);
+        let gapped = [transaction(1, 2, 100), transaction(1, 3, 100)];
+        for transaction in [&head, &gapped[0], &gapped[1]] {
+            add(&mut mempool, &chain, transaction);
+        }
+
+        assert_eq!(
+            mempool.pending().map(|tx| tx.hash).collect::<Vec<_>>(),
+            [head.hash]
+        );
+        assert_eq!(
+            mempool.queued().map(|tx| tx.hash).collect::<Vec<_>>(),
+            hashes(&gapped)
+        );
+        assert_eq!(
+            hashes(&mempool.select_for_block(u64::MAX, chain.next_base_fee())),
+            [head.hash]
+        );
+
+        let filler = transaction(1, 1, 100);
+        add(&mut mempool, &chain, &filler);
+        assert_eq!(mempool.pending().count(), 4);
+        assert_eq!(mempool.queued().count(), 0);
+    }
+
+    #[test]
+    fn sender_whose_next_transaction_does_not_fit_is_skipped() {
+        let chain = funded_chain(&[1, 2]);
+        let mut mempool = Mempool::new();
+        let first = transaction_with_gas(1, 0, 21_000, 500);
+        let too_big = transaction_with_gas(1, 1, 50_000, 500);
+        let after_big = transaction_with_gas(1, 2, 21_000, 500);
+        let other = transaction_with_gas(2, 0, 21_000, 100);
+        for transaction in [&first, &too_big, &after_big, &other] {
+            add(&mut mempool, &chain, transaction);
+        }
+
+        let selected = mempool.select_for_block(45_000, chain.next_base_fee());
+
+        assert_eq!(hashes(&selected), [first.hash, other.hash]);
+    }
+
+    #[test]
+    fn transactions_below_base_fee_are_not_selected() {
+        let chain = funded_chain(&[1, 2]);
+        let mut mempool = Mempool::new();
+        let low = transaction_with_fees(1, 0, 1_000_000_000, 100);
+        let follower = transaction_with_fees(1, 1, 4_000_000_000, 100);
+        let high = transaction_with_fees(2, 0, 4_000_000_000, 100);
+        for transaction in [&low, &follower, &high] {
+            add(&mut mempool, &chain, transaction);
+        }
+
+        let selected = mempool.select_for_block(u64::MAX, 2_000_000_000);
+
+        assert_eq!(hashes(&selected), [high.hash]);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use crate::transaction::Transaction;
+
+#[derive(Debug, Default, Clone)]
+pub struct SenderQueue {
+    base_nonce: u64,
+    transactions: BTreeMap<u64, Transaction>,
+}
+
+impl SenderQueue {
+    pub fn new(base_nonce: u64) -> Self {
+        Self {
+            base_nonce,
+            transactions: BTreeMap::new(),
+        }
+    }
+
+    pub fn base_nonce(&self) -> u64 {
+        self.base_nonce
+    }
+
+    pub fn set_base_nonce(&mut self, base_nonce: u64) {
+        self.base_nonce = base_nonce;
+    }
+
+    pub fn get(&self, nonce: u64) -> Option<&Transaction> {
+        self.transactions.get(&nonce)
+    }
+
+    pub fn insert(&mut self, transaction: Transaction) -> Option<Transaction> {
+        self.transactions.insert(transaction.nonce, transaction)
+    }
+
+    pub fn remove(&mut self, nonce: u64) -> Option<Transaction> {
+        self.transactions.remove(&nonce)
+    }
+
+    pub fn last(&self) -> Option<&Transaction> {
+        self.transactions.values().next_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.values()
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions
+            .range(self.base_nonce..)
+            .zip(self.base_nonce..)
+            .take_while(|((nonce, _), expected)| **nonce == *expected)
+            .map(|((_, transaction), _)| transaction)
+    }
+
+    pub fn queued(&self) -> impl Iterator<Item = &Transaction> {
+        let pending = self.pending().count() as u64;
+        self.transactions
+            .range(self.base_nonce.saturating_add(pending)..)
+            .map(|(_, transaction)| transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::SecretKey;
+
+    use super::*;
+    use crate::transaction::UnsignedTransaction;
+
+    fn transaction(nonce: u64) -> Transaction {
+        UnsignedTransaction {
+            chain_id: 1,
+            nonce,
+            gas_limit: 21_000,
+            ..UnsignedTransaction::default()
+        }
+        .sign(&SecretKey::from_byte_array([1; 32]).unwrap())
+    }
+
+    fn queue(base_nonce: u64, nonces: &[u64]) -> SenderQueue {
+        let mut queue = SenderQueue::new(base_nonce);
+        for nonce in nonces {
+            queue.insert(transaction(*nonce));
+        }
+        queue
+    }
+
+    fn nonces<'a>(transactions: impl Iterator<Item = &'a Transaction>) -> Vec<u64> {
+        transactions.map(|transaction| transaction.nonce).collect()
+    }
+
+    #[test]
+    fn contiguous_run_from_base_nonce_is_pending() {
+        let queue = queue(3, &[3, 4, 5]);
+
+        assert_eq!(nonces(queue.pending()), [3, 4, 5]);
+        assert!(nonces(queue.queued()).is_empty());
+    }
+
+    #[test]
+    fn gap_moves_later_nonces_to_queued() {
+        let queue = queue(3, &[3, 4, 6, 7]);
+
+        assert_eq!(nonces(queue.pending()), [3, 4]);
+        assert_eq!(nonces(queue.queued()), [6, 7]);
+    }
+
+    #[test]
+    fn missing_base_nonce_queues_everything() {
+        let mut queue = queue(3, &[4, 5]);
+
+        assert!(nonces(queue.pending()).is_empty());
+        assert_eq!(nonces(queue.queued()), [4, 5]);
+
+        queue.insert(transaction(3));
+        assert_eq!(nonces(queue.pending()), [3, 4, 5]);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::types::Hash;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+const ROOT_PREFIX: u8 = 0x02;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub leaf_count: usize,
+    pub siblings: Vec<Hash>,
+}
+
+impl MerkleProof {
+    pub fn verify(&self, leaf: &Hash, root: &Hash) -> bool {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+
+        let mut current = hash_leaf(leaf);
+        let mut index = self.index;
+        let mut width = self.leaf_count;
+        let mut siblings = self.siblings.iter();
+
+        while width > 1 {
+            let is_left = index.is_multiple_of(2);
+            if !is_left || index + 1 < width {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                current = if is_left {
+                    hash_node(&current, sibling)
+                } else {
+                    hash_node(sibling, &current)
+                };
+            }
+
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+
+        siblings.next().is_none() && hash_root(&current, self.leaf_count) == *root
+    }
+}
+
+pub fn merkle_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return Hash::zero();
+    }
+
+    let mut level: Vec<Hash> = leaves.iter().map(hash_leaf).collect();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    hash_root(&level[0], leaves.len())
+}
+
+pub fn build_proof(leaves: &[Hash], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut level: Vec<Hash> = leaves.iter().map(hash_leaf).collect();
+    let mut position = index;
+    let mut siblings = Vec::new();
+
+    while level.len() > 1 {
+        let sibling = position ^ 1;
+        if sibling < level.len() {
+            siblings.push(level[sibling]);
+        }
+
+        level = next_level(&level);
+        position /= 2;
+    }
+
+    Some(MerkleProof {
+        index,
+        leaf_count: leaves.len(),
+        siblings,
+    })
+}
+
+fn next_level(level: &[Hash]) -> Vec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) yields one or two items"),
+        })
+        .collect()
+}
+
+fn hash_leaf(leaf: &Hash) -> Hash {
+    let mut payload = Vec::with_capacity(33);
+    payload.push(LEAF_PREFIX);
+    payload.extend_from_slice(leaf.as_bytes());
+    Hash::keccak(&payload)
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut payload = Vec::with_capacity(65);
+    payload.push(NODE_PREFIX);
+    payload.extend_from_slice(left.as_bytes());
+    payload.extend_from_slice(right.as_bytes());
+    Hash::keccak(&payload)
+}
+
+/// Binds the leaf count into the root: with odd nodes promoted unchanged, a
+/// tree of 3 leaves and one of 4 would otherwise share proofs for leaf 0.
+fn hash_root(top: &Hash, leaf_count: usize) -> Hash {
+    let mut payload = Vec::with_capacity(41);
+    payload.push(ROOT_PREFIX);
+    payload.extend_from_slice(&(leaf_count as u64).to_be_bytes());
+    payload.extend_from_slice(top.as_bytes());
+    Hash::keccak(&payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: usize) -> Vec<Hash> {
+        (0..count).map(|i| Hash::keccak(&[i as u8])).collect()
+    }
+
+    fn expected_root(leaves: &[Hash]) -> Hash {
+        let l: Vec<Hash> = leaves.iter().map(hash_leaf).collect();
+        let top = match l.len() {
+            1 => l[0],
+            2 => hash_node(&l[0], &l[1]),
+            3 => hash_node(&hash_node(&l[0], &l[1]), &l[2]),
+            4 => hash_node(&hash_node(&l[0], &l[1]), &hash_node(&l[2], &l[3])),
+            5 => hash_node(
+                &hash_node(&hash_node(&l[0], &l[1]), &hash_node(&l[2], &l[3])),
+                &l[4],
+            ),
+            _ => unreachable!("tests cover up to five leaves"),
+        };
+        hash_root(&top, l.len())
+    }
+
+    #[test]
+    fn empty_tree_has_zero_root() {
+        assert_eq!(merkle_root(&[]), Hash::zero());
+        assert_eq!(build_proof(&[], 0), None);
+    }
+
+    #[test]
+    fn root_matches_hand_built_tree() {
+        for count in 1..=5 {
+            let leaves = leaves(count);
+            assert_eq!(
+                merkle_root(&leaves),
+                expected_root(&leaves),
+                "{count} leaves"
+            );
+        }
+    }
+
+    #[test]
+    fn proof_verifies_for_every_leaf() {
+        for count in 1..=5 {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = build_proof(&leaves, index).unwrap();
+                assert!(proof.verify(leaf, &root), "{count} leaves, index {index}");
+            }
+            assert_eq!(build_proof(&leaves, count), None);
+        }
+    }
+
+    #[test]
+    fn tampered_proof_is_rejected() {
+        for count in 1..=5 {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = build_proof(&leaves, index).unwrap();
+
+                for sibling in 0..proof.siblings.len() {
+                    let mut tampered = proof.clone();
+                    tampered.siblings[sibling] = Hash::keccak(b"tampered");
+                    assert!(!tampered.verify(leaf, &root));
+                }
+
+                for other in (0..count + 1).filter(|other| *other != index) {
+                    let mut tampered = proof.clone();
+                    tampered.index = other;
+                    assert!(
+                        !tampered.verify(leaf, &root),
+                        "{count} leaves, index {index} as {other}"
+                    );
+                }
+
+                for leaf_count in (index..count + 2).filter(|n| *n != count) {
+                    let mut tampered = proof.clone();
+                    tampered.leaf_count = leaf_count;
+                    assert!(
+                        !tampered.verify(leaf, &root),
+                        "{count} leaves, index {index} as count {leaf_count}"
+                    );
+                }
+
+                assert!(!proof.verify(&Hash::keccak(b"other leaf"), &root));
+            }
+        }
+    }
+}
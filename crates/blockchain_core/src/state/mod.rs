@@ -0,0 +1,303 @@
+pub mod undo;
+
+pub use undo::*;
+
+use std::collections::BTreeMap;
+
+use rlp::RlpStream;
+
+use crate::config::NodeConfig;
+use crate::error::StateError;
+use crate::transaction::Transaction;
+use crate::types::{Address, Hash};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Account {
+    pub balance: u64,
+    pub nonce: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionOutcome {
+    pub sender: Address,
+    pub recipient: Address,
+    pub gas_used: u64,
+    pub tip: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorldState {
+    accounts: BTreeMap<Address, Account>,
+}
+
+impl WorldState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn genesis(config: &NodeConfig) -> Self {
+        let mut state = Self::new();
+        for (address, balance) in &config.genesis_alloc {
+            state.set_account(
+                *address,
+                Account {
+                    balance: *balance,
+                    nonce: 0,
+                },
+            );
+        }
+        state
+    }
+
+    pub fn account(&self, address: &Address) -> Account {
+        self.accounts.get(address).copied().unwrap_or_default()
+    }
+
+    pub fn get_account(&self, address: &Address) -> Option<Account> {
+        self.accounts.get(address).copied()
+    }
+
+    pub fn set_account(&mut self, address: Address, account: Account) {
+        self.accounts.insert(address, account);
+    }
+
+    pub fn remove_account(&mut self, address: &Address) {
+        self.accounts.remove(address);
+    }
+
+    pub fn credit(&mut self, address: &Address, amount: u64) -> Result<(), StateError> {
+        let mut account = self.account(address);
+        account.balance = account
+            .balance
+            .checked_add(amount)
+            .ok_or(StateError::BalanceOverflow)?;
+        self.set_account(*address, account);
+        Ok(())
+    }
+
+    pub fn debit(&mut self, address: &Address, amount: u64) -> Result<(), StateError> {
+        let mut account = self.account(address);
+        account.balance = account
+            .balance
+            .checked_sub(amount)
+            .ok_or(StateError::InsufficientBalance)?;
+        self.set_account(*address, account);
+        Ok(())
+    }
+
+    pub fn apply_transaction(
+        &mut self,
+        transaction: &Transaction,
+        sender: Address,
+        base_fee: u64,
+    ) -> Result<TransactionOutcome, StateError> {
+        let mut account = self.account(&sender);
+
+        if transaction.nonce < account.nonce {
+            return Err(StateError::NonceTooLow);
+        }
+
+        if transaction.nonce > account.nonce {
+            return Err(StateError::NonceGap);
+        }
+
+        let tip = transaction
+            .effective_tip(base_fee)
+            .ok_or(StateError::FeeBelowBaseFee)?;
+        let gas_used = transaction.intrinsic_gas();
+        let gas_cost = gas_used
+            .checked_mul(base_fee + tip)
+            .ok_or(StateError::InsufficientBalance)?;
+        let total_cost = gas_cost
+            .checked_add(transaction.value)
+            .ok_or(StateError::InsufficientBalance)?;
+
+        account.balance = account
+            .balance
+            .checked_sub(total_cost)
+            .ok_or(StateError::InsufficientBalance)?;
+        account.nonce += 1;
+        let recipient = transaction
+            .to
+            .unwrap_or_else(|| Transaction::contract_address(&sender, transaction.nonce));
+        // Checked before the sender is debited so a failed transaction leaves
+        // the state untouched. Paying oneself only lowers the balance.
+        if recipient != sender {
+            self.account(&recipient)
+                .balance
+                .checked_add(transaction.value)
+                .ok_or(StateError::BalanceOverflow)?;
+        }
+
+        self.set_account(sender, account);
+        self.credit(&recipient, transaction.value)?;
+
+        Ok(TransactionOutcome {
+            sender,
+            recipient,
+            gas_used,
+            tip: gas_used * tip,
+        })
+    }
+
+    pub fn state_root(&self) -> Hash {
+        let mut stream = RlpStream::new_list(self.accounts.len());
+        for (address, account) in &self.accounts {
+            stream.begin_list(3);
+            stream.append(&address.as_bytes().to_vec());
+            stream.append(&account.balance);
+            stream.append(&account.nonce);
+        }
+        Hash::keccak(&stream.out())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::SecretKey;
+
+    use super::*;
+    use crate::transaction::UnsignedTransaction;
+
+    const BASE_FEE: u64 = 100;
+    const TIP: u64 = 7;
+    const GAS: u64 = 21_000;
+
+    fn key() -> SecretKey {
+        SecretKey::from_byte_array([3; 32]).unwrap()
+    }
+
+    fn sender() -> Address {
+        Address::from_secret_key(&key())
+    }
+
+    fn recipient() -> Address {
+        Address::from_bytes([4; 20])
+    }
+
+    fn transfer(to: Address, nonce: u64, value: u64) -> Transaction {
+        UnsignedTransaction {
+            chain_id: 1,
+            nonce,
+            to: Some(to),
+            value,
+            gas_limit: GAS,
+            max_fee_per_gas: 1_000,
+            max_priority_fee_per_gas: TIP,
+            ..UnsignedTransaction::default()
+        }
+        .sign(&key())
+    }
+
+    fn funded(balance: u64, nonce: u64) -> WorldState {
+        let mut state = WorldState::new();
+        state.set_account(sender(), Account { balance, nonce });
+        state
+    }
+
+    fn apply(
+        state: &mut WorldState,
+        transaction: &Transaction,
+    ) -> Result<TransactionOutcome, StateError> {
+        state.apply_transaction(transaction, sender(), BASE_FEE)
+    }
+
+    #[test]
+    fn transfer_burns_base_fee_and_reports_tip() {
+        let mut state = funded(10_000_000, 0);
+
+        let outcome = apply(&mut state, &transfer(recipient(), 0, 500)).unwrap();
+
+        assert_eq!(
+            outcome,
+            TransactionOutcome {
+                sender: sender(),
+                recipient: recipient(),
+                gas_used: GAS,
+                tip: GAS * TIP,
+            }
+        );
+        let paid = 500 + GAS * (BASE_FEE + TIP);
+        assert_eq!(
+            state.account(&sender()),
+            Account {
+                balance: 10_000_000 - paid,
+                nonce: 1
+            }
+        );
+        assert_eq!(state.account(&recipient()).balance, 500);
+        // The base fee leaves circulation; the tip is credited to the miner
+        // by block execution.
+        let total: u64 = [sender(), recipient()]
+            .iter()
+            .map(|address| state.account(address).balance)
+            .sum();
+        assert_eq!(total + GAS * BASE_FEE + outcome.tip, 10_000_000);
+    }
+
+    #[test]
+    fn nonce_must_match_account() {
+        let mut state = funded(10_000_000, 1);
+
+        assert_eq!(
+            apply(&mut state, &transfer(recipient(), 0, 1)),
+            Err(StateError::NonceTooLow)
+        );
+        assert_eq!(
+            apply(&mut state, &transfer(recipient(), 2, 1)),
+            Err(StateError::NonceGap)
+        );
+        assert_eq!(state, funded(10_000_000, 1));
+    }
+
+    #[test]
+    fn balance_must_cover_value_and_gas() {
+        let cost = 500 + GAS * (BASE_FEE + TIP);
+
+        let mut state = funded(cost - 1, 0);
+        assert_eq!(
+            apply(&mut state, &transfer(recipient(), 0, 500)),
+            Err(StateError::InsufficientBalance)
+        );
+        assert_eq!(state, funded(cost - 1, 0));
+
+        let mut state = funded(cost, 0);
+        apply(&mut state, &transfer(recipient(), 0, 500)).unwrap();
+        assert_eq!(state.account(&sender()).balance, 0);
+    }
+
+    #[test]
+    fn recipient_overflow_leaves_state_untouched() {
+        let mut state = funded(10_000_000, 0);
+        state.set_account(
+            recipient(),
+            Account {
+                balance: u64::MAX,
+                nonce: 0,
+            },
+        );
+        let before = state.clone();
+
+        assert_eq!(
+            apply(&mut state, &transfer(recipient(), 0, 1)),
+            Err(StateError::BalanceOverflow)
+        );
+        assert_eq!(state, before);
+    }
+
+    #[test]
+    fn sender_paying_itself_only_spends_gas() {
+        let mut state = funded(10_000_000, 0);
+
+        let outcome = apply(&mut state, &transfer(sender(), 0, 5_000)).unwrap();
+
+        assert_eq!(outcome.recipient, sender());
+        assert_eq!(
+            state.account(&sender()),
+            Account {
+                balance: 10_000_000 - GAS * (BASE_FEE + TIP),
+                nonce: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use std::collections::BTreeSet;
+
+use crate::state::{Account, WorldState};
+use crate::types::Address;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BlockUndo {
+    pub accounts: Vec<(Address, Option<Account>)>,
+}
+
+impl BlockUndo {
+    pub fn capture(state: &WorldState, touched: &BTreeSet<Address>) -> Self {
+        let accounts = touched
+            .iter()
+            .map(|address| (*address, state.get_account(address)))
+            .collect();
+        Self { accounts }
+    }
+}
+
+impl WorldState {
+    pub fn apply_undo(&mut self, undo: &BlockUndo) {
+        for (address, previous) in &undo.accounts {
+            match previous {
+                Some(account) => self.set_account(*address, *account),
+                None => self.remove_account(address),
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use rlp::{DecoderError, Rlp, RlpStream};
+
+use crate::block::{Block, BlockHeader};
+use crate::state::{Account, BlockUndo};
+use crate::storage::StoredBlock;
+use crate::transaction::Transaction;
+use crate::types::{Address, Hash, Timestamp};
+
+pub fn encode_chain(blocks: &[StoredBlock]) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(blocks.len());
+    for stored in blocks {
+        append_stored_block(&mut stream, stored);
+    }
+    stream.out().to_vec()
+}
+
+pub fn decode_chain(bytes: &[u8]) -> Result<Vec<StoredBlock>, DecoderError> {
+    let rlp = Rlp::new(bytes);
+    let info = rlp.payload_info()?;
+    if info.header_len + info.value_len != bytes.len() {
+        return Err(DecoderError::Custom("trailing or missing chain bytes"));
+    }
+
+    list_items(&rlp)?.iter().map(decode_stored_block).collect()
+}
+
+fn append_stored_block(stream: &mut RlpStream, stored: &StoredBlock) {
+    stream.begin_list(4);
+    append_header(stream, &stored.block.header);
+    stream.begin_list(stored.block.transactions.len());
+    for transaction in &stored.block.transactions {
+        append_transaction(stream, transaction);
+    }
+    append_undo(stream, &stored.undo);
+    stream.begin_list(stored.senders.len());
+    for sender in &stored.senders {
+        stream.append(&sender.as_bytes().to_vec());
+    }
+}
+
+fn decode_stored_block(rlp: &Rlp) -> Result<StoredBlock, DecoderError> {
+    expect_list(rlp, 4)?;
+    let header = decode_header(&rlp.at(0)?)?;
+    let transactions = list_items(&rlp.at(1)?)?
+        .iter()
+        .map(decode_transaction)
+        .collect::<Result<Vec<_>, _>>()?;
+    let undo = decode_undo(&rlp.at(2)?)?;
+    let senders = list_items(&rlp.at(3)?)?
+        .iter()
+        .map(|item| {
+            let bytes: Vec<u8> = item.as_val()?;
+            bytes
+                .try_into()
+                .map(Address::from_bytes)
+                .map_err(|_| DecoderError::Custom("unexpected fixed field length"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let block = Block {
+        header,
+        transactions,
+    };
+    block
+        .validate_body()
+        .map_err(|_| DecoderError::Custom("transactions root mismatch"))?;
+    Ok(StoredBlock {
+        block,
+        undo,
+        senders,
+    })
+}
+
+fn append_header(stream: &mut RlpStream, header: &BlockHeader) {
+    stream.begin_list(12);
+    stream.append(&header.parent_hash.as_bytes().to_vec());
+    stream.append(&header.number);
+    stream.append(&header.timestamp.as_u64());
+    stream.append(&header.transactions_root.as_bytes().to_vec());
+    stream.append(&header.state_root.as_bytes().to_vec());
+    stream.append(&header.bits);
+    stream.append(&header.nonce);
+    stream.append(&header.extra_nonce);
+    stream.append(&header.miner.as_bytes().to_vec());
+    stream.append(&header.gas_used);
+    stream.append(&header.gas_limit);
+    stream.append(&header.base_fee_per_gas);
+}
+
+fn decode_header(rlp: &Rlp) -> Result<BlockHeader, DecoderError> {
+    expect_list(rlp, 12)?;
+    Ok(BlockHeader {
+        parent_hash: Hash::from_bytes(fixed_bytes(rlp, 0)?),
+        number: rlp.val_at(1)?,
+        timestamp: Timestamp::new(rlp.val_at(2)?),
+        transactions_root: Hash::from_bytes(fixed_bytes(rlp, 3)?),
+        state_root: Hash::from_bytes(fixed_bytes(rlp, 4)?),
+        bits: rlp.val_at(5)?,
+        nonce: rlp.val_at(6)?,
+        extra_nonce: rlp.val_at(7)?,
+        miner: Address::from_bytes(fixed_bytes(rlp, 8)?),
+        gas_used: rlp.val_at(9)?,
+        gas_limit: rlp.val_at(10)?,
+        base_fee_per_gas: rlp.val_at(11)?,
+    })
+}
+
+fn append_transaction(stream: &mut RlpStream, transaction: &Transaction) {
+    stream.append(&transaction.to_rlp_bytes());
+}
+
+fn decode_transaction(rlp: &Rlp) -> Result<Transaction, DecoderError> {
+    let bytes: Vec<u8> = rlp.as_val()?;
+    Transaction::from_rlp_bytes(&bytes).map_err(|_| DecoderError::Custom("invalid transaction"))
+}
+
+fn append_undo(stream: &mut RlpStream, undo: &BlockUndo) {
+    stream.begin_list(undo.accounts.len());
+    for (address, previous) in &undo.accounts {
+        stream.begin_list(4);
+        stream.append(&address.as_bytes().to_vec());
+        stream.append(&previous.is_some());
+        let account = previous.unwrap_or_default();
+        stream.append(&account.balance);
+        stream.append(&account.nonce);
+    }
+}
+
+fn decode_undo(rlp: &Rlp) -> Result<BlockUndo, DecoderError> {
+    let accounts = list_items(rlp)?
+        .into_iter()
+        .map(|item| {
+            expect_list(&item, 4)?;
+            let address = Address::from_bytes(fixed_bytes(&item, 0)?);
+            let existed: bool = item.val_at(1)?;
+            let account = Account {
+                balance: item.val_at(2)?,
+                nonce: item.val_at(3)?,
+            };
+            Ok((address, existed.then_some(account)))
+        })
+        .collect::<Result<Vec<_>, DecoderError>>()?;
+    Ok(BlockUndo { accounts })
+}
+
+/// Every item of a list; unlike `Rlp::iter`, a malformed item is an error
+/// instead of silently ending the iteration.
+fn list_items<'a>(rlp: &Rlp<'a>) -> Result<Vec<Rlp<'a>>, DecoderError> {
+    if !rlp.is_list() {
+        return Err(DecoderError::RlpExpectedToBeList);
+    }
+
+    let items: Vec<Rlp<'a>> = rlp.iter().collect();
+    let consumed: usize = items.iter().map(|item| item.as_raw().len()).sum();
+    if consumed != rlp.payload_info()?.value_len {
+        return Err(DecoderError::Custom("malformed list item"));
+    }
+    Ok(items)
+}
+
+fn expect_list(rlp: &Rlp, len: usize) -> Result<(), DecoderError> {
+    if list_items(rlp)?.len() != len {
+        return Err(DecoderError::RlpIncorrectListLen);
+    }
+    Ok(())
+}
+
+fn fixed_bytes<const N: usize>(rlp: &Rlp, index: usize) -> Result<[u8; N], DecoderError> {
+    let bytes: Vec<u8> = rlp.val_at(index)?;
+    bytes
+        .try_into()
+        .map_err(|_| DecoderError::Custom("unexpected fixed field length"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    fn stored_block() -> StoredBlock {
+        let config = NodeConfig::default();
+        let miner = Address::from_bytes([9_u8; 20]);
+        let mut header = Block::genesis(&config).header;
+        header.number = 1;
+        header.miner = miner;
+        let coinbase = Transaction::coinbase(config.chain_id, 1, miner, 50);
+        StoredBlock {
+            block: Block::new(header, vec![coinbase]),
+            undo: BlockUndo {
+                accounts: vec![
+                    (miner, None),
+                    (
+                        Address::from_bytes([2_u8; 20]),
+                        Some(Account {
+                            balance: 7,
+                            nonce: 1,
+                        }),
+                    ),
+                ],
+            },
+            senders: vec![Address::from_bytes([3_u8; 20])],
+        }
+    }
+
+    /// Chain of one stored block whose field at `index` is replaced by `raw`.
+    fn chain_with_field(index: usize, raw: &[u8]) -> Vec<u8> {
+        let encoded = encode_chain(&[stored_block()]);
+        let chain = Rlp::new(&encoded);
+        let block = chain.at(0).unwrap();
+        let mut stream = RlpStream::new_list(1);
+        stream.begin_list(4);
+        for field in 0..4 {
+            if field == index {
+                stream.append_raw(raw, 1);
+            } else {
+                stream.append_raw(block.at(field).unwrap().as_raw(), 1);
+            }
+        }
+        stream.out().to_vec()
+    }
+
+    #[test]
+    fn chain_round_trips() {
+        let stored = stored_block();
+
+        let decoded = decode_chain(&encode_chain(&[stored.clone(), stored.clone()])).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        for block in decoded {
+            assert_eq!(block.block.hash(), stored.block.hash());
+            assert_eq!(block.undo, stored.undo);
+            assert_eq!(block.senders, stored.senders);
+        }
+        assert!(decode_chain(&encode_chain(&[])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn garbage_and_truncated_bytes_are_rejected() {
+        let encoded = encode_chain(&[stored_block()]);
+
+        assert!(decode_chain(b"hello world").is_err());
+        assert!(decode_chain(&[]).is_err());
+        assert!(decode_chain(&encoded[..encoded.len() - 1]).is_err());
+        assert!(decode_chain(&encoded[..encoded.len() / 2]).is_err());
+
+        let mut trailing = encoded;
+        trailing.push(0x80);
+        assert!(decode_chain(&trailing).is_err());
+    }
+
+    #[test]
+    fn malformed_nested_items_are_rejected() {
+        let truncated_item = [0xc1, 0xb8];
+
+        assert!(decode_chain(&chain_with_field(1, &truncated_item)).is_err());
+        assert!(decode_chain(&chain_with_field(2, &truncated_item)).is_err());
+        assert!(decode_chain(&chain_with_field(3, &truncated_item)).is_err());
+        assert!(decode_chain(&chain_with_field(2, &[0x80])).is_err());
+    }
+}
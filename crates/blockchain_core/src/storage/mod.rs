@@ -1,9 +1,24 @@
+pub mod codec;
+
+use std::fs;
+use std::path::Path;
+
 use crate::block::Block;
-use crate::error::BlockchainError;
+use crate::error::{BlockchainError, StorageError};
+use crate::state::BlockUndo;
+use crate::types::Address;
+
+#[derive(Debug, Clone)]
+pub struct StoredBlock {
+    pub block: Block,
+    pub undo: BlockUndo,
+    /// Recovered senders of the non-coinbase transactions, in block order.
+    pub senders: Vec<Address>,
+}
 
 pub trait ChainStorage {
-    fn save(&self, blocks: &[Block]) -> Result<(), BlockchainError>;
-    fn load(&self) -> Result<Vec<Block>, BlockchainError>;
+    fn save(&self, blocks: &[StoredBlock]) -> Result<(), BlockchainError>;
+    fn load(&self) -> Result<Vec<StoredBlock>, BlockchainError>;
 }
 
 #[derive(Debug, Clone)]
@@ -13,19 +28,60 @@ pub struct FileStorage {
 
 impl FileStorage {
     pub fn new(path: impl Into<String>) -> Self {
-        let _ = path;
-        todo!("file storage constructor implementation is not added yet")
+        Self { path: path.into() }
     }
 }
 
 impl ChainStorage for FileStorage {
-    fn save(&self, blocks: &[Block]) -> Result<(), BlockchainError> {
-        let _ = (self, blocks);
-        todo!("save implementation is not added yet")
+    fn save(&self, blocks: &[StoredBlock]) -> Result<(), BlockchainError> {
+        let bytes = codec::encode_chain(blocks);
+        let temp_path = format!("{}.tmp", self.path);
+        fs::write(&temp_path, bytes).map_err(|e| StorageError::Io(e.to_string()))?;
+        fs::rename(&temp_path, &self.path).map_err(|e| StorageError::Io(e.to_string()))?;
+        Ok(())
     }
 
-    fn load(&self) -> Result<Vec<Block>, BlockchainError> {
-        let _ = self;
-        todo!("load implementation is not added yet")
+    fn load(&self) -> Result<Vec<StoredBlock>, BlockchainError> {
+        if !Path::new(&self.path).exists() {
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(&self.path).map_err(|e| StorageError::Io(e.to_string()))?;
+        let blocks =
+            codec::decode_chain(&bytes).map_err(|e| StorageError::Corrupted(e.to_string()))?;
+        Ok(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("blockchain-core-{name}-{}.bin", std::process::id()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn missing_file_loads_as_empty_chain() {
+        let storage = FileStorage::new(temp_path("missing"));
+
+        assert!(storage.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn corrupted_file_is_an_error() {
+        let path = temp_path("corrupted");
+        fs::write(&path, b"hello world").unwrap();
+
+        let loaded = FileStorage::new(path.clone()).load();
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(
+            loaded,
+            Err(BlockchainError::Storage(StorageError::Corrupted(_)))
+        ));
     }
 }
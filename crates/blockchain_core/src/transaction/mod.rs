@@ -1,8 +1,12 @@
-use rlp::RlpStream;
+pub mod unsigned;
+
+pub use unsigned::*;
+
+use rlp::{DecoderError, Rlp, RlpStream};
 use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
 use secp256k1::{Message, Secp256k1};
 
-use crate::error::TransactionError;
+use crate::error::{TransactionError, TypeError};
 use crate::types::{Address, Hash, Signature};
 
 #[derive(Debug, Clone)]
@@ -10,27 +14,36 @@ pub struct Transaction {
     pub hash: Hash,
     pub chain_id: u64,
     pub nonce: u64,
-    pub to: Address,
+    pub to: Option<Address>,
     pub value: u64,
     pub gas_limit: u64,
     pub max_fee_per_gas: u64,
     pub max_priority_fee_per_gas: u64,
     pub data: Vec<u8>,
+    pub access_list: Vec<(Address, Vec<Hash>)>,
     pub signature: Signature,
 }
 
 impl Transaction {
     const TYPE_BYTE: u8 = 0x02;
+    const BASE_GAS: u64 = 21_000;
+    const ZERO_BYTE_GAS: u64 = 4;
+    const NON_ZERO_BYTE_GAS: u64 = 16;
+    const CONTRACT_CREATION_GAS: u64 = 32_000;
+    const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+    const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
 
+    #[allow(clippy::too_many_arguments)]
     fn unsigned_payload(
         chain_id: u64,
         nonce: u64,
-        to: &Address,
+        to: Option<&Address>,
         value: u64,
         gas_limit: u64,
         max_fee_per_gas: u64,
         max_priority_fee_per_gas: u64,
         data: &[u8],
+        access_list: &[(Address, Vec<Hash>)],
     ) -> Vec<u8> {
         let mut stream = RlpStream::new_list(9);
         stream.append(&chain_id);
@@ -38,22 +51,24 @@ impl Transaction {
         stream.append(&max_priority_fee_per_gas);
         stream.append(&max_fee_per_gas);
         stream.append(&gas_limit);
-        stream.append(&to.as_bytes().to_vec());
+        append_recipient(&mut stream, to);
         stream.append(&value);
         stream.append(&data.to_vec());
-        stream.begin_list(0);
+        append_access_list(&mut stream, access_list);
         stream.out().to_vec()
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn signed_payload(
         chain_id: u64,
         nonce: u64,
-        to: &Address,
+        to: Option<&Address>,
         value: u64,
         gas_limit: u64,
         max_fee_per_gas: u64,
         max_priority_fee_per_gas: u64,
         data: &[u8],
+        access_list: &[(Address, Vec<Hash>)],
         signature: &Signature,
     ) -> Vec<u8> {
         let mut stream = RlpStream::new_list(12);
@@ -62,32 +77,38 @@ impl Transaction {
         stream.append(&max_priority_fee_per_gas);
         stream.append(&max_fee_per_gas);
         stream.append(&gas_limit);
-        stream.append(&to.as_bytes().to_vec());
+        append_recipient(&mut stream, to);
         stream.append(&value);
         stream.append(&data.to_vec());
-        stream.begin_list(0);
+        append_access_list(&mut stream, access_list);
         stream.append(&u8::from(signature.y_parity()));
         stream.append(&trim_leading_zeroes(signature.r()));
         stream.append(&trim_leading_zeroes(signature.s()));
         stream.out().to_vec()
     }
 
-    fn prefixed_hash(payload: Vec<u8>) -> Hash {
+    fn typed_payload(payload: Vec<u8>) -> Vec<u8> {
         let mut typed_payload = Vec::with_capacity(payload.len() + 1);
         typed_payload.push(Self::TYPE_BYTE);
         typed_payload.extend(payload);
-        Hash::keccak(&typed_payload)
+        typed_payload
+    }
+
+    fn prefixed_hash(payload: Vec<u8>) -> Hash {
+        Hash::keccak(&Self::typed_payload(payload))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn calculate_signing_hash(
         chain_id: u64,
         nonce: u64,
-        to: &Address,
+        to: Option<&Address>,
         value: u64,
         gas_limit: u64,
         max_fee_per_gas: u64,
         max_priority_fee_per_gas: u64,
         data: &[u8],
+        access_list: &[(Address, Vec<Hash>)],
     ) -> Hash {
         let payload = Self::unsigned_payload(
             chain_id,
@@ -98,19 +119,22 @@ impl Transaction {
             max_fee_per_gas,
             max_priority_fee_per_gas,
             data,
+            access_list,
         );
         Self::prefixed_hash(payload)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn calculate_hash(
         chain_id: u64,
         nonce: u64,
-        to: &Address,
+        to: Option<&Address>,
         value: u64,
         gas_limit: u64,
         max_fee_per_gas: u64,
         max_priority_fee_per_gas: u64,
         data: &[u8],
+        access_list: &[(Address, Vec<Hash>)],
         signature: &Signature,
     ) -> Hash {
         let payload = Self::signed_payload(
@@ -122,31 +146,35 @@ impl Transaction {
             max_fee_per_gas,
             max_priority_fee_per_gas,
             data,
+            access_list,
             signature,
         );
         Self::prefixed_hash(payload)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         chain_id: u64,
         nonce: u64,
-        to: Address,
+        to: Option<Address>,
         value: u64,
         gas_limit: u64,
         max_fee_per_gas: u64,
         max_priority_fee_per_gas: u64,
         data: Vec<u8>,
+        access_list: Vec<(Address, Vec<Hash>)>,
         signature: Signature,
     ) -> Self {
         let hash = Self::calculate_hash(
             chain_id,
             nonce,
-            &to,
+            to.as_ref(),
             value,
             gas_limit,
             max_fee_per_gas,
             max_priority_fee_per_gas,
             &data,
+            &access_list,
             &signature,
         );
 
@@ -160,24 +188,75 @@ impl Transaction {
             max_fee_per_gas,
             max_priority_fee_per_gas,
             data,
+            access_list,
             signature,
         }
     }
 
+    pub fn coinbase(chain_id: u64, height: u64, miner: Address, reward: u64) -> Self {
+        let signature = Signature::new([0_u8; 32], [0_u8; 32], false);
+        Self::new(
+            chain_id,
+            height,
+            Some(miner),
+            reward,
+            0,
+            0,
+            0,
+            Vec::new(),
+            Vec::new(),
+            signature,
+        )
+    }
+
+    pub fn is_coinbase(&self) -> bool {
+        self.signature.r() == &[0_u8; 32] && self.signature.s() == &[0_u8; 32]
+    }
+
+    pub fn is_contract_creation(&self) -> bool {
+        self.to.is_none()
+    }
+
+    /// Address of the contract deployed by `sender` with the given nonce:
+    /// the last 20 bytes of `keccak(rlp([sender, nonce]))`.
+    pub fn contract_address(sender: &Address, nonce: u64) -> Address {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&sender.as_bytes().to_vec());
+        stream.append(&nonce);
+        let hash = Hash::keccak(&stream.out());
+        let mut raw = [0_u8; 20];
+        raw.copy_from_slice(&hash.as_bytes()[12..]);
+        Address::from_bytes(raw)
+    }
+
+    pub fn created_address(&self) -> Result<Option<Address>, TransactionError> {
+        if !self.is_contract_creation() {
+            return Ok(None);
+        }
+
+        let sender = self.recover_signer()?;
+        Ok(Some(Self::contract_address(&sender, self.nonce)))
+    }
+
     pub fn signing_hash(&self) -> Hash {
         Self::calculate_signing_hash(
             self.chain_id,
             self.nonce,
-            &self.to,
+            self.to.as_ref(),
             self.value,
             self.gas_limit,
             self.max_fee_per_gas,
             self.max_priority_fee_per_gas,
             &self.data,
+            &self.access_list,
         )
     }
 
     pub fn recover_signer(&self) -> Result<Address, TransactionError> {
+        self.signature.validate().map_err(|err| match err {
+            TypeError::HighSignatureS => TransactionError::HighSignatureS,
+            _ => TransactionError::SignatureOutOfRange,
+        })?;
         let signing_hash = self.signing_hash();
         let message = Message::from_digest(*signing_hash.as_bytes());
         let recovery_id = RecoveryId::try_from(i32::from(self.signature.recovery_id()))
@@ -192,7 +271,87 @@ impl Transaction {
         Ok(Address::from_public_key(&serialized[1..]))
     }
 
-    pub fn validate(&self) -> Result<(), TransactionError> {
+    pub fn to_rlp_bytes(&self) -> Vec<u8> {
+        let payload = Self::signed_payload(
+            self.chain_id,
+            self.nonce,
+            self.to.as_ref(),
+            self.value,
+            self.gas_limit,
+            self.max_fee_per_gas,
+            self.max_priority_fee_per_gas,
+            &self.data,
+            &self.access_list,
+            &self.signature,
+        );
+        Self::typed_payload(payload)
+    }
+
+    pub fn from_rlp_bytes(bytes: &[u8]) -> Result<Self, TransactionError> {
+        let (type_byte, payload) = bytes
+            .split_first()
+            .ok_or(TransactionError::InvalidEnvelope)?;
+        if *type_byte != Self::TYPE_BYTE {
+            return Err(TransactionError::InvalidEnvelope);
+        }
+
+        let rlp = Rlp::new(payload);
+        if !rlp.is_list() {
+            return Err(TransactionError::MalformedRlp);
+        }
+
+        let info = rlp.payload_info().map_err(decoder_error)?;
+        if info.header_len + info.value_len != payload.len() {
+            return Err(TransactionError::NonCanonicalEncoding);
+        }
+
+        if rlp.item_count().map_err(decoder_error)? != 12 {
+            return Err(TransactionError::InvalidFieldCount);
+        }
+
+        let to: Vec<u8> = rlp.val_at(5).map_err(decoder_error)?;
+        let to = match to.len() {
+            0 => None,
+            20 => Some(Address::from_bytes(
+                to.try_into().expect("length is checked above"),
+            )),
+            _ => return Err(TransactionError::InvalidFieldLength),
+        };
+
+        let access_list = decode_access_list(&rlp.at(8).map_err(decoder_error)?)?;
+
+        let y_parity = match rlp.val_at::<u8>(9).map_err(decoder_error)? {
+            0 => false,
+            1 => true,
+            _ => return Err(TransactionError::InvalidSignature),
+        };
+        let signature =
+            Signature::new(decode_scalar(&rlp, 10)?, decode_scalar(&rlp, 11)?, y_parity);
+
+        Ok(Self::new(
+            rlp.val_at(0).map_err(decoder_error)?,
+            rlp.val_at(1).map_err(decoder_error)?,
+            to,
+            rlp.val_at(6).map_err(decoder_error)?,
+            rlp.val_at(4).map_err(decoder_error)?,
+            rlp.val_at(3).map_err(decoder_error)?,
+            rlp.val_at(2).map_err(decoder_error)?,
+            rlp.val_at(7).map_err(decoder_error)?,
+            access_list,
+            signature,
+        ))
+    }
+
+    /// Full validation; returns the recovered sender so callers do not have
+    /// to repeat the signature recovery.
+    pub fn validate(&self) -> Result<Address, TransactionError> {
+        self.validate_fields()?;
+        self.recover_signer()
+    }
+
+    /// Checks everything except the signature, for transactions whose
+    /// validity is already vouched for by a checkpoint.
+    pub fn validate_fields(&self) -> Result<(), TransactionError> {
         if self.chain_id == 0 {
             return Err(TransactionError::InvalidChainId);
         }
@@ -201,6 +360,10 @@ impl Transaction {
             return Err(TransactionError::InvalidGasLimit);
         }
 
+        if self.gas_limit < self.intrinsic_gas() {
+            return Err(TransactionError::IntrinsicGasTooLow);
+        }
+
         if self.max_priority_fee_per_gas > self.max_fee_per_gas {
             return Err(TransactionError::PriorityFeeExceedsMaxFee);
         }
@@ -208,12 +371,13 @@ impl Transaction {
         let expected_hash = Self::calculate_hash(
             self.chain_id,
             self.nonce,
-            &self.to,
+            self.to.as_ref(),
             self.value,
             self.gas_limit,
             self.max_fee_per_gas,
             self.max_priority_fee_per_gas,
             &self.data,
+            &self.access_list,
             &self.signature,
         );
 
@@ -221,16 +385,127 @@ impl Transaction {
             return Err(TransactionError::InvalidHash);
         }
 
-        self.recover_signer()?;
-
         Ok(())
     }
 
+    pub fn intrinsic_gas(&self) -> u64 {
+        let base_gas = if self.is_contract_creation() {
+            Self::BASE_GAS + Self::CONTRACT_CREATION_GAS
+        } else {
+            Self::BASE_GAS
+        };
+        let data_gas = self.data.iter().fold(base_gas, |gas, byte| {
+            let byte_gas = if *byte == 0 {
+                Self::ZERO_BYTE_GAS
+            } else {
+                Self::NON_ZERO_BYTE_GAS
+            };
+            gas.saturating_add(byte_gas)
+        });
+        self.access_list
+            .iter()
+            .fold(data_gas, |gas, (_, storage_keys)| {
+                let keys_gas =
+                    (storage_keys.len() as u64).saturating_mul(Self::ACCESS_LIST_STORAGE_KEY_GAS);
+                gas.saturating_add(Self::ACCESS_LIST_ADDRESS_GAS)
+                    .saturating_add(keys_gas)
+            })
+    }
+
+    pub fn effective_tip(&self, base_fee: u64) -> Option<u64> {
+        let headroom = self.max_fee_per_gas.checked_sub(base_fee)?;
+        Some(self.max_priority_fee_per_gas.min(headroom))
+    }
+
+    pub fn max_cost(&self) -> Option<u64> {
+        self.gas_limit
+            .checked_mul(self.max_fee_per_gas)?
+            .checked_add(self.value)
+    }
+
     pub fn is_valid(&self) -> bool {
         self.validate().is_ok()
     }
 }
 
+fn append_recipient(stream: &mut RlpStream, to: Option<&Address>) {
+    match to {
+        Some(address) => stream.append(&address.as_bytes().to_vec()),
+        None => stream.append_empty_data(),
+    };
+}
+
+fn append_access_list(stream: &mut RlpStream, access_list: &[(Address, Vec<Hash>)]) {
+    stream.begin_list(access_list.len());
+    for (address, storage_keys) in access_list {
+        stream.begin_list(2);
+        stream.append(&address.as_bytes().to_vec());
+        stream.begin_list(storage_keys.len());
+        for key in storage_keys {
+            stream.append(&key.as_bytes().to_vec());
+        }
+    }
+}
+
+fn decode_access_list(rlp: &Rlp) -> Result<Vec<(Address, Vec<Hash>)>, TransactionError> {
+    if !rlp.is_list() {
+        return Err(TransactionError::InvalidAccessList);
+    }
+
+    rlp.iter()
+        .map(|entry| {
+            if !entry.is_list() || entry.item_count().map_err(decoder_error)? != 2 {
+                return Err(TransactionError::InvalidAccessList);
+            }
+
+            let address: Vec<u8> = entry.val_at(0).map_err(decoder_error)?;
+            let address: [u8; 20] = address
+                .try_into()
+                .map_err(|_| TransactionError::InvalidAccessList)?;
+            let keys = entry.at(1).map_err(decoder_error)?;
+            if !keys.is_list() {
+                return Err(TransactionError::InvalidAccessList);
+            }
+
+            let storage_keys = keys
+                .iter()
+                .map(|key| {
+                    let key: Vec<u8> = key.as_val().map_err(decoder_error)?;
+                    key.try_into()
+                        .map(Hash::from_bytes)
+                        .map_err(|_| TransactionError::InvalidAccessList)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((Address::from_bytes(address), storage_keys))
+        })
+        .collect()
+}
+
+fn decode_scalar(rlp: &Rlp, index: usize) -> Result<[u8; 32], TransactionError> {
+    let bytes: Vec<u8> = rlp.val_at(index).map_err(decoder_error)?;
+    if bytes.len() > 32 {
+        return Err(TransactionError::InvalidFieldLength);
+    }
+
+    if bytes.first() == Some(&0) {
+        return Err(TransactionError::NonCanonicalEncoding);
+    }
+
+    let mut scalar = [0_u8; 32];
+    scalar[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(scalar)
+}
+
+fn decoder_error(err: DecoderError) -> TransactionError {
+    match err {
+        DecoderError::RlpDataLenWithZeroPrefix
+        | DecoderError::RlpListLenWithZeroPrefix
+        | DecoderError::RlpInvalidIndirection => TransactionError::NonCanonicalEncoding,
+        DecoderError::RlpIncorrectListLen => TransactionError::InvalidFieldCount,
+        _ => TransactionError::MalformedRlp,
+    }
+}
+
 fn trim_leading_zeroes(bytes: &[u8; 32]) -> Vec<u8> {
     let first_non_zero = bytes
         .iter()
@@ -238,3 +513,349 @@ fn trim_leading_zeroes(bytes: &[u8; 32]) -> Vec<u8> {
         .unwrap_or(bytes.len());
     bytes[first_non_zero..].to_vec()
 }
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::SecretKey;
+
+    use super::*;
+
+    /// EIP-1559 transfer from Ethereum mainnet.
+    const MAINNET_RAW: &str = "02f86f0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf52d495ab9c9680841b55ba3ac080a0c199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039a028ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8";
+    const MAINNET_HASH: &str = "0xce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31";
+    const MAINNET_SENDER: &str = "0x001e2b7de757ba469a57bf6b23d982458a07efce";
+
+    fn mainnet_bytes() -> Vec<u8> {
+        hex::decode(MAINNET_RAW).unwrap()
+    }
+
+    fn mainnet_fields() -> Vec<Vec<u8>> {
+        let bytes = mainnet_bytes();
+        Rlp::new(&bytes[1..])
+            .iter()
+            .map(|item| item.as_raw().to_vec())
+            .collect()
+    }
+
+    fn envelope(fields: &[Vec<u8>]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(fields.len());
+        for field in fields {
+            stream.append_raw(field, 1);
+        }
+        Transaction::typed_payload(stream.out().to_vec())
+    }
+
+    fn decode_with_field(index: usize, raw: Vec<u8>) -> Result<Transaction, TransactionError> {
+        let mut fields = mainnet_fields();
+        fields[index] = raw;
+        Transaction::from_rlp_bytes(&envelope(&fields))
+    }
+
+    #[test]
+    fn mainnet_transaction_decodes_with_known_hash() {
+        let transaction = Transaction::from_rlp_bytes(&mainnet_bytes()).unwrap();
+
+        assert_eq!(transaction.hash, Hash::from_hex(MAINNET_HASH).unwrap());
+        assert_eq!(
+            transaction.recover_signer().unwrap(),
+            Address::from_hex(MAINNET_SENDER).unwrap()
+        );
+        assert_eq!(transaction.chain_id, 1);
+        assert_eq!(transaction.nonce, 2);
+        assert_eq!(transaction.to_rlp_bytes(), mainnet_bytes());
+        assert_eq!(envelope(&mainnet_fields()), mainnet_bytes());
+    }
+
+    #[test]
+    fn envelope_type_must_be_eip1559() {
+        let mut bytes = mainnet_bytes();
+        bytes[0] = 0x01;
+
+        assert_eq!(
+            Transaction::from_rlp_bytes(&bytes).unwrap_err(),
+            TransactionError::InvalidEnvelope
+        );
+        assert_eq!(
+            Transaction::from_rlp_bytes(&[]).unwrap_err(),
+            TransactionError::InvalidEnvelope
+        );
+    }
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let mut bytes = mainnet_bytes();
+        bytes.push(0x00);
+
+        assert_eq!(
+            Transaction::from_rlp_bytes(&bytes).unwrap_err(),
+            TransactionError::NonCanonicalEncoding
+        );
+    }
+
+    #[test]
+    fn leading_zero_scalars_are_rejected() {
+        assert_eq!(
+            decode_with_field(1, vec![0x82, 0x00, 0x02]).unwrap_err(),
+            TransactionError::NonCanonicalEncoding
+        );
+
+        let mut r = mainnet_fields()[10][1..].to_vec();
+        r[0] = 0x00;
+        let raw = rlp::encode(&r).to_vec();
+        assert_eq!(
+            decode_with_field(10, raw).unwrap_err(),
+            TransactionError::NonCanonicalEncoding
+        );
+    }
+
+    #[test]
+    fn long_form_single_byte_is_rejected() {
+        assert_eq!(
+            decode_with_field(1, vec![0x81, 0x02]).unwrap_err(),
+            TransactionError::NonCanonicalEncoding
+        );
+    }
+
+    #[test]
+    fn field_count_must_be_twelve() {
+        let mut fields = mainnet_fields();
+        fields.pop();
+        assert_eq!(
+            Transaction::from_rlp_bytes(&envelope(&fields)).unwrap_err(),
+            TransactionError::InvalidFieldCount
+        );
+
+        let mut fields = mainnet_fields();
+        fields.push(vec![0x80]);
+        assert_eq!(
+            Transaction::from_rlp_bytes(&envelope(&fields)).unwrap_err(),
+            TransactionError::InvalidFieldCount
+        );
+    }
+
+    #[test]
+    fn recipient_must_be_empty_or_twenty_bytes() {
+        let mut to = mainnet_fields()[5].clone();
+        to.pop();
+        to[0] = 0x80 + 19;
+
+        assert_eq!(
+            decode_with_field(5, to).unwrap_err(),
+            TransactionError::InvalidFieldLength
+        );
+    }
+
+    fn hash32(input: &str) -> Hash {
+        Hash::from_hex(input).unwrap()
+    }
+
+    fn scalar(input: &str) -> [u8; 32] {
+        hex::decode(input).unwrap().try_into().unwrap()
+    }
+
+    /// Signed EIP-1559 transaction with one access-list entry, from the
+    /// Ropsten test network (chain id 3).
+    fn access_list_transaction() -> Transaction {
+        UnsignedTransaction {
+            chain_id: 3,
+            nonce: 2,
+            to: Some(Address::from_hex("0x8210357f377e901f18e45294e86a2a32215cc3c9").unwrap()),
+            value: 0x7b,
+            gas_limit: 0x6a40,
+            max_fee_per_gas: 0x3b9a_ca0e,
+            max_priority_fee_per_gas: 0x3b9a_ca00,
+            data: Vec::new(),
+            access_list: vec![(
+                Address::from_hex("0x8ba1f109551bd432803012645ac136ddd64dba72").unwrap(),
+                vec![
+                    hash32("0x0000000000000000000000000000000000000000000000000000000000000000"),
+                    hash32("0x0000000000000000000000000000000000000000000000000000000000000042"),
+                ],
+            )],
+        }
+        .with_signature(Signature::new(
+            scalar("f13b5088108f783f4b6048d4be456971118aabfb88be96bb541d734b6c2b20dc"),
+            scalar("13fb7eb25a7d5df42a176cd4c6a086e19163ed7cd8ffba015f939d24f66bc17a"),
+            true,
+        ))
+    }
+
+    #[test]
+    fn access_list_transaction_matches_reference() {
+        let transaction = access_list_transaction();
+
+        assert_eq!(
+            transaction.hash,
+            hash32("0x824384376c5972498c6fcafe71fd8cad1689f64e7d5e270d025a898638c0c34d")
+        );
+        assert_eq!(
+            transaction.recover_signer().unwrap(),
+            Address::from_hex("0x541d6a0e9ca9e7a083e41e2e178eef9f22d7492e").unwrap()
+        );
+        assert_eq!(transaction.intrinsic_gas(), 21_000 + 2_400 + 2 * 1_900);
+
+        let decoded = Transaction::from_rlp_bytes(&transaction.to_rlp_bytes()).unwrap();
+        assert_eq!(decoded.hash, transaction.hash);
+        assert_eq!(decoded.access_list, transaction.access_list);
+    }
+
+    #[test]
+    fn access_list_entries_must_be_well_formed() {
+        let transaction = access_list_transaction();
+        let bytes = transaction.to_rlp_bytes();
+        let mut fields: Vec<Vec<u8>> = Rlp::new(&bytes[1..])
+            .iter()
+            .map(|item| item.as_raw().to_vec())
+            .collect();
+        let mut entry = RlpStream::new_list(2);
+        entry.append(&vec![0x11_u8; 19]);
+        entry.begin_list(0);
+        let mut list = RlpStream::new_list(1);
+        list.append_raw(&entry.out(), 1);
+        fields[8] = list.out().to_vec();
+
+        assert_eq!(
+            Transaction::from_rlp_bytes(&envelope(&fields)).unwrap_err(),
+            TransactionError::InvalidAccessList
+        );
+    }
+
+    fn resigned(transaction: &Transaction, signature: Signature) -> Transaction {
+        let mut transaction = transaction.clone();
+        transaction.signature = signature;
+        Transaction::from_rlp_bytes(&transaction.to_rlp_bytes()).unwrap()
+    }
+
+    #[test]
+    fn malleated_signature_is_rejected_as_high_s() {
+        let transaction = Transaction::from_rlp_bytes(&mainnet_bytes()).unwrap();
+        let malleated = resigned(&transaction, transaction.signature.malleated());
+
+        assert_ne!(malleated.hash, transaction.hash);
+        assert_eq!(
+            malleated.recover_signer().unwrap_err(),
+            TransactionError::HighSignatureS
+        );
+        assert_eq!(
+            malleated.validate().unwrap_err(),
+            TransactionError::HighSignatureS
+        );
+    }
+
+    #[test]
+    fn signature_scalars_must_be_in_range() {
+        let transaction = Transaction::from_rlp_bytes(&mainnet_bytes()).unwrap();
+        let r = *transaction.signature.r();
+        let s = *transaction.signature.s();
+        let order = scalar("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+
+        for (r, s) in [(order, s), (r, order)] {
+            let signature = Signature::new(r, s, transaction.signature.y_parity());
+            assert_eq!(
+                resigned(&transaction, signature).validate().unwrap_err(),
+                TransactionError::SignatureOutOfRange
+            );
+        }
+
+        // Zero scalars encode as empty strings, so they are built directly.
+        for (r, s) in [([0_u8; 32], s), (r, [0_u8; 32])] {
+            let mut zeroed = transaction.clone();
+            zeroed.signature = Signature::new(r, s, true);
+            assert_eq!(
+                zeroed.recover_signer().unwrap_err(),
+                TransactionError::SignatureOutOfRange
+            );
+        }
+    }
+
+    fn unsigned_with_data(to: Option<Address>, data: Vec<u8>) -> UnsignedTransaction {
+        UnsignedTransaction {
+            chain_id: 1,
+            to,
+            gas_limit: 100_000,
+            data,
+            ..UnsignedTransaction::default()
+        }
+    }
+
+    fn signed(unsigned: UnsignedTransaction) -> Transaction {
+        unsigned.sign(&SecretKey::from_byte_array([5; 32]).unwrap())
+    }
+
+    #[test]
+    fn intrinsic_gas_charges_data_bytes_and_creation() {
+        let recipient = Some(Address::from_bytes([1; 20]));
+        let cases = [
+            (recipient, vec![], 21_000),
+            (recipient, vec![0, 0, 0], 21_000 + 3 * 4),
+            (recipient, vec![1, 0xff], 21_000 + 2 * 16),
+            (recipient, vec![0, 1, 0, 2], 21_000 + 2 * 4 + 2 * 16),
+            (None, vec![], 21_000 + 32_000),
+            (None, vec![0, 0x60], 21_000 + 32_000 + 4 + 16),
+        ];
+
+        for (to, data, expected) in cases {
+            let transaction = signed(unsigned_with_data(to, data));
+            assert_eq!(transaction.intrinsic_gas(), expected);
+        }
+    }
+
+    #[test]
+    fn gas_limit_below_intrinsic_gas_is_rejected() {
+        let unsigned = unsigned_with_data(None, vec![0, 0x60]);
+        let intrinsic = signed(unsigned.clone()).intrinsic_gas();
+
+        let short = signed(UnsignedTransaction {
+            gas_limit: intrinsic - 1,
+            ..unsigned.clone()
+        });
+        assert_eq!(
+            short.validate().unwrap_err(),
+            TransactionError::IntrinsicGasTooLow
+        );
+
+        let exact = signed(UnsignedTransaction {
+            gas_limit: intrinsic,
+            ..unsigned
+        });
+        assert!(exact.validate().is_ok());
+    }
+
+    #[test]
+    fn contract_address_matches_known_vectors() {
+        let sender = Address::from_hex("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap();
+        let expected = [
+            "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d",
+            "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8",
+            "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91",
+        ];
+
+        for (nonce, address) in expected.into_iter().enumerate() {
+            assert_eq!(
+                Transaction::contract_address(&sender, nonce as u64).to_string(),
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn deployment_round_trips_through_rlp() {
+        let deployment = signed(UnsignedTransaction {
+            nonce: 3,
+            ..unsigned_with_data(None, vec![0x60, 0x80, 0x60, 0x40, 0x52])
+        });
+
+        let decoded = Transaction::from_rlp_bytes(&deployment.to_rlp_bytes()).unwrap();
+
+        assert!(decoded.is_contract_creation());
+        assert_eq!(decoded.to, None);
+        assert_eq!(decoded.hash, deployment.hash);
+        assert_eq!(decoded.data, deployment.data);
+        assert_eq!(decoded.to_rlp_bytes(), deployment.to_rlp_bytes());
+        let sender = decoded.validate().unwrap();
+        assert_eq!(
+            decoded.created_address().unwrap(),
+            Some(Transaction::contract_address(&sender, 3))
+        );
+    }
+}
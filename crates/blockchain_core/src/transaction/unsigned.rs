@@ -0,0 +1,91 @@
+use secp256k1::{Message, Secp256k1, SecretKey};
+
+use crate::transaction::Transaction;
+use crate::types::{Address, Hash, Signature};
+
+/// Transaction fields covered by the signature; signing produces the final
+/// [`Transaction`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub to: Option<Address>,
+    pub value: u64,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u64,
+    pub max_priority_fee_per_gas: u64,
+    pub data: Vec<u8>,
+    pub access_list: Vec<(Address, Vec<Hash>)>,
+}
+
+impl UnsignedTransaction {
+    pub fn signing_hash(&self) -> Hash {
+        Transaction::calculate_signing_hash(
+            self.chain_id,
+            self.nonce,
+            self.to.as_ref(),
+            self.value,
+            self.gas_limit,
+            self.max_fee_per_gas,
+            self.max_priority_fee_per_gas,
+            &self.data,
+            &self.access_list,
+        )
+    }
+
+    pub fn sign(self, secret_key: &SecretKey) -> Transaction {
+        let message = Message::from_digest(*self.signing_hash().as_bytes());
+        let (recovery_id, compact) = Secp256k1::new()
+            .sign_ecdsa_recoverable(message, secret_key)
+            .serialize_compact();
+        let mut r = [0_u8; 32];
+        let mut s = [0_u8; 32];
+        r.copy_from_slice(&compact[..32]);
+        s.copy_from_slice(&compact[32..]);
+        self.with_signature(Signature::new(r, s, i32::from(recovery_id) == 1))
+    }
+
+    pub fn with_signature(self, signature: Signature) -> Transaction {
+        Transaction::new(
+            self.chain_id,
+            self.nonce,
+            self.to,
+            self.value,
+            self.gas_limit,
+            self.max_fee_per_gas,
+            self.max_priority_fee_per_gas,
+            self.data,
+            self.access_list,
+            signature,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_transaction_recovers_signer() {
+        let secret_key = SecretKey::from_byte_array([7_u8; 32]).unwrap();
+        let unsigned = UnsignedTransaction {
+            chain_id: 1,
+            nonce: 3,
+            to: Some(Address::from_bytes([2_u8; 20])),
+            value: 10,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2,
+            max_priority_fee_per_gas: 1,
+            ..UnsignedTransaction::default()
+        };
+        let signing_hash = unsigned.signing_hash();
+
+        let transaction = unsigned.sign(&secret_key);
+
+        assert_eq!(transaction.signing_hash(), signing_hash);
+        assert_eq!(
+            transaction.recover_signer().unwrap(),
+            Address::from_secret_key(&secret_key)
+        );
+    }
+}
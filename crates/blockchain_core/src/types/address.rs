@@ -1,8 +1,9 @@
 use crate::error::TypeError;
 use crate::utils::hex::decode_fixed_hex;
+use secp256k1::{Secp256k1, SecretKey};
 use sha3::{Digest, Keccak256};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Address([u8; 20]);
 
 impl Address {
@@ -10,6 +11,14 @@ impl Address {
         Self::from_hex(input)
     }
 
+    pub fn zero() -> Self {
+        Self([0_u8; 20])
+    }
+
+    pub fn from_bytes(raw: [u8; 20]) -> Self {
+        Self(raw)
+    }
+
     pub fn from_hex(input: &str) -> Result<Self, TypeError> {
         let raw = decode_fixed_hex(
             input,
@@ -27,6 +36,11 @@ impl Address {
         Self(raw)
     }
 
+    pub fn from_secret_key(secret_key: &SecretKey) -> Self {
+        let public_key = secret_key.public_key(&Secp256k1::new());
+        Self::from_public_key(&public_key.serialize_uncompressed()[1..])
+    }
+
     pub fn as_bytes(&self) -> &[u8; 20] {
         &self.0
     }
@@ -11,6 +11,14 @@ impl Hash {
         Self::keccak(input.as_bytes())
     }
 
+    pub fn zero() -> Self {
+        Self([0_u8; 32])
+    }
+
+    pub fn from_bytes(raw: [u8; 32]) -> Self {
+        Self(raw)
+    }
+
     pub fn keccak(input: &[u8]) -> Self {
         let mut hasher = Keccak256::new();
         hasher.update(input);
@@ -2,8 +2,10 @@ pub mod address;
 pub mod hash;
 pub mod signature;
 pub mod timestamp;
+pub mod uint;
 
 pub use address::*;
 pub use hash::*;
 pub use signature::*;
 pub use timestamp::*;
+pub use uint::*;
@@ -1,6 +1,15 @@
 use crate::error::TypeError;
 use crate::utils::hex::decode_fixed_hex;
 
+const CURVE_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+const HALF_CURVE_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Signature {
     r: [u8; 32],
@@ -31,7 +40,28 @@ impl Signature {
             _ => return Err(TypeError::InvalidRecoveryId),
         };
 
-        Ok(Self { r, s, y_parity })
+        let signature = Self { r, s, y_parity };
+        signature.validate()?;
+        Ok(signature)
+    }
+
+    /// Rejects scalars outside `[1, n)` and high-S values (EIP-2), so every
+    /// payload has exactly one valid signature encoding.
+    pub fn validate(&self) -> Result<(), TypeError> {
+        let zero = [0_u8; 32];
+        if self.r == zero || self.s == zero || self.r >= CURVE_ORDER || self.s >= CURVE_ORDER {
+            return Err(TypeError::SignatureOutOfRange);
+        }
+
+        if !self.is_low_s() {
+            return Err(TypeError::HighSignatureS);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_low_s(&self) -> bool {
+        self.s <= HALF_CURVE_ORDER
     }
 
     pub fn r(&self) -> &[u8; 32] {
@@ -71,3 +101,66 @@ impl core::fmt::Display for Signature {
         f.write_str(&self.to_hex())
     }
 }
+
+#[cfg(test)]
+impl Signature {
+    /// The second encoding `(r, n - s, !y)` that verifies for the same
+    /// message and key.
+    pub(crate) fn malleated(&self) -> Self {
+        let n = crate::types::U256::from_big_endian(&CURVE_ORDER);
+        let s = n - crate::types::U256::from_big_endian(&self.s);
+        Self::new(self.r, s.to_big_endian(), !self.y_parity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn low_s_signature() -> Signature {
+        let mut r = [0_u8; 32];
+        let mut s = [0_u8; 32];
+        r[31] = 1;
+        s[31] = 2;
+        Signature::new(r, s, false)
+    }
+
+    #[test]
+    fn scalars_outside_curve_order_are_out_of_range() {
+        let valid = low_s_signature();
+        let zero = [0_u8; 32];
+        for signature in [
+            Signature::new(zero, *valid.s(), false),
+            Signature::new(*valid.r(), zero, false),
+            Signature::new(CURVE_ORDER, *valid.s(), false),
+            Signature::new(*valid.r(), CURVE_ORDER, false),
+        ] {
+            assert_eq!(signature.validate(), Err(TypeError::SignatureOutOfRange));
+        }
+        assert_eq!(valid.validate(), Ok(()));
+    }
+
+    #[test]
+    fn half_order_is_the_highest_low_s() {
+        let signature = Signature::new(*low_s_signature().r(), HALF_CURVE_ORDER, false);
+        assert_eq!(signature.validate(), Ok(()));
+
+        let mut above_half = HALF_CURVE_ORDER;
+        above_half[31] += 1;
+        let signature = Signature::new(*low_s_signature().r(), above_half, false);
+        assert_eq!(signature.validate(), Err(TypeError::HighSignatureS));
+    }
+
+    #[test]
+    fn from_hex_rejects_high_s() {
+        let signature = low_s_signature();
+        assert_eq!(Signature::from_hex(&signature.to_hex()), Ok(signature));
+
+        let malleated = signature.malleated();
+        assert!(!malleated.is_low_s());
+        assert_eq!(
+            Signature::from_hex(&malleated.to_hex()),
+            Err(TypeError::HighSignatureS)
+        );
+    }
+}
@@ -1,4 +1,8 @@
-#[derive(Debug, Clone)]
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Timestamp(u64);
 
 impl Timestamp {
@@ -6,7 +10,110 @@ impl Timestamp {
         Self(input)
     }
 
+    pub fn now() -> Self {
+        Self::from(SystemTime::now())
+    }
+
     pub fn as_u64(&self) -> u64 {
         self.0
     }
+
+    pub fn saturating_sub(self, seconds: u64) -> Self {
+        Self(self.0.saturating_sub(seconds))
+    }
+
+    pub fn seconds_since(self, earlier: Timestamp) -> u64 {
+        self.0.saturating_sub(earlier.0)
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self(seconds)
+    }
+}
+
+impl From<u64> for Timestamp {
+    fn from(input: u64) -> Self {
+        Self(input)
+    }
+}
+
+impl Add<u64> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, seconds: u64) -> Self::Output {
+        Self(self.0.saturating_add(seconds))
+    }
+}
+
+impl Sub<u64> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, seconds: u64) -> Self::Output {
+        self.saturating_sub(seconds)
+    }
+}
+
+impl core::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> Timestamp;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub Timestamp);
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_saturates() {
+        let time = Timestamp::new(100);
+
+        assert_eq!(time + 20, Timestamp::new(120));
+        assert_eq!(time - 20, Timestamp::new(80));
+        assert_eq!(time - 200, Timestamp::new(0));
+        assert_eq!(Timestamp::new(u64::MAX) + 1, Timestamp::new(u64::MAX));
+        assert_eq!(time.saturating_sub(101), Timestamp::default());
+    }
+
+    #[test]
+    fn seconds_since_never_goes_negative() {
+        assert_eq!(Timestamp::new(150).seconds_since(Timestamp::new(100)), 50);
+        assert_eq!(Timestamp::new(100).seconds_since(Timestamp::new(150)), 0);
+    }
+
+    #[test]
+    fn conversions_and_ordering() {
+        assert_eq!(Timestamp::from(42), Timestamp::new(42));
+        assert_eq!(Timestamp::from(UNIX_EPOCH).as_u64(), 0);
+        assert!(Timestamp::new(1) < Timestamp::new(2));
+        assert_eq!(Timestamp::new(42).to_string(), "42");
+        assert_eq!(FixedClock(Timestamp::new(5)).now(), Timestamp::new(5));
+    }
 }
@@ -0,0 +1,7 @@
+#![allow(clippy::manual_div_ceil)]
+
+use uint::construct_uint;
+
+construct_uint! {
+    pub struct U256(4);
+}
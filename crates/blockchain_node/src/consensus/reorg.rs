@@ -1,3 +1,11 @@
-pub fn try_reorg() -> Result<(), String> {
-    todo!("reorg strategy is not added yet")
+use blockchain_core::blockchain::{Blockchain, ChainUpdate};
+use blockchain_core::mempool::Mempool;
+
+pub fn apply_chain_update(chain: &Blockchain, mempool: &mut Mempool, update: &ChainUpdate) {
+    for block in update.disconnected.iter().rev() {
+        mempool.on_block_disconnected(block, chain);
+    }
+    for block in &update.connected {
+        mempool.on_block_connected(block, chain);
+    }
 }
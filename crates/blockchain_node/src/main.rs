@@ -1,5 +1,7 @@
 mod consensus;
 mod mining;
+// Peer-to-peer transport is not implemented yet.
+#[allow(dead_code)]
 mod network;
 mod node;
 mod storage;
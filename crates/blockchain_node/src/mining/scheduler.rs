@@ -1,5 +1,7 @@
+#[allow(dead_code)]
 pub struct MiningScheduler;
 
+#[allow(dead_code)]
 impl MiningScheduler {
     pub fn should_mine() -> bool {
         todo!("mining scheduling is not added yet")
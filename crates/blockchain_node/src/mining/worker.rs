@@ -1,7 +1,153 @@
-pub struct MiningWorker;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use blockchain_core::block::Block;
+use blockchain_core::config::NodeConfig;
+use blockchain_core::consensus::{CancelToken, MiningReport, MiningStatus, PowConsensus};
+use blockchain_core::types::{Clock, SystemClock};
+
+#[derive(Debug, Clone)]
+pub struct MiningWorker {
+    consensus: PowConsensus,
+    threads: usize,
+    job: Arc<Mutex<CancelToken>>,
+    chain_height: Arc<AtomicU64>,
+    clock: Arc<dyn Clock>,
+}
 
 impl MiningWorker {
-    pub fn mine_once() -> Result<(), String> {
-        todo!("mining worker is not added yet")
+    pub fn new(config: &NodeConfig) -> Result<Self, String> {
+        let consensus = PowConsensus::new(config).map_err(|e| {
+            format!(
+                "невалидный pow_limit_bits {:#010x}: {e}",
+                config.pow_limit_bits
+            )
+        })?;
+
+        Ok(Self {
+            consensus,
+            threads: config.mining_threads,
+            job: Arc::new(Mutex::new(CancelToken::new())),
+            chain_height: Arc::new(AtomicU64::new(0)),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    pub fn mine_once(&self, block: &mut Block) -> Result<MiningReport, String> {
+        let cancel = CancelToken::new();
+        *self.job.lock().expect("mining job lock poisoned") = cancel.clone();
+
+        // Checked after the new job is installed: a block announced earlier is
+        // visible here, a later one cancels the installed token.
+        if block.number() != self.chain_height.load(Ordering::SeqCst) + 1 {
+            return Err("шаблон блока устарел".to_string());
+        }
+
+        let report = self
+            .consensus
+            .mine_block(block, self.threads, &cancel, self.clock.as_ref())
+            .ok_or_else(|| format!("невалидный target блока: {:#010x}", block.header.bits))?;
+
+        match report.status {
+            MiningStatus::Found(_) => Ok(report),
+            MiningStatus::Cancelled => Err("майнинг отменён".to_string()),
+            MiningStatus::Exhausted => Err("пространство nonce исчерпано".to_string()),
+        }
+    }
+
+    /// Records the height of the current tip. A reorganization onto a shorter
+    /// but heavier branch lowers it, so templates built on the old tip go stale.
+    pub fn on_new_block(&self, height: u64) {
+        self.chain_height.store(height, Ordering::SeqCst);
+        self.cancel();
+    }
+
+    pub fn cancel(&self) {
+        self.job.lock().expect("mining job lock poisoned").cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn block_announced_before_mining_is_not_lost() {
+        let config = NodeConfig::default();
+        let worker = MiningWorker::new(&config).unwrap();
+        let mut block = Block::genesis(&config);
+        block.header.number = 1;
+
+        worker.on_new_block(1);
+
+        assert!(worker.mine_once(&mut block).is_err());
+    }
+
+    #[test]
+    fn mining_above_chain_height_succeeds() {
+        let config = NodeConfig {
+            mining_threads: 1,
+            ..NodeConfig::default()
+        };
+        let worker = MiningWorker::new(&config).unwrap();
+        let mut block = Block::genesis(&config);
+        block.header.number = 2;
+
+        worker.on_new_block(1);
+        let report = worker.mine_once(&mut block).unwrap();
+
+        assert!(matches!(report.status, MiningStatus::Found(_)));
+    }
+
+    #[test]
+    fn tip_height_follows_a_reorg_to_a_shorter_chain() {
+        let config = NodeConfig {
+            mining_threads: 1,
+            ..NodeConfig::default()
+        };
+        let worker = MiningWorker::new(&config).unwrap();
+        let mut stale = Block::genesis(&config);
+        stale.header.number = 6;
+        let mut block = Block::genesis(&config);
+        block.header.number = 4;
+
+        worker.on_new_block(5);
+        worker.on_new_block(3);
+
+        assert!(worker.mine_once(&mut stale).is_err());
+        assert!(worker.mine_once(&mut block).is_ok());
+    }
+
+    #[test]
+    fn cancel_from_another_thread_aborts_mining() {
+        let config = NodeConfig {
+            mining_threads: 2,
+            ..NodeConfig::default()
+        };
+        let worker = MiningWorker::new(&config).unwrap();
+        let mut block = Block::genesis(&config);
+        block.header.number = 1;
+        block.header.bits = 0x1d00_ffff;
+        let done = AtomicBool::new(false);
+
+        let result = thread::scope(|scope| {
+            scope.spawn(|| {
+                // The job token is replaced when mining starts, so keep
+                // cancelling until the search has observed it.
+                while !done.load(Ordering::SeqCst) {
+                    worker.cancel();
+                    thread::sleep(Duration::from_millis(10));
+                }
+            });
+            let result = worker.mine_once(&mut block);
+            done.store(true, Ordering::SeqCst);
+            result
+        });
+
+        assert_eq!(result.unwrap_err(), "майнинг отменён");
     }
 }
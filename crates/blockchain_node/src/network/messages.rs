@@ -1,9 +1,11 @@
+use blockchain_core::block::Block;
+
 #[derive(Debug, Clone)]
 pub enum NetworkMessage {
     Ping,
     Pong,
     NewTransaction,
-    NewBlock,
+    NewBlock(Block),
     GetHeight,
     Height(u64),
     GetBlocks { from: u64 },
@@ -1,11 +1,109 @@
+use std::env;
+
 use blockchain_core::config::NodeConfig;
+use blockchain_core::storage::FileStorage;
+
+pub const DEFAULT_CHAIN_PATH: &str = "chain.db";
 
 pub struct NodeContext {
     pub config: NodeConfig,
+    pub storage: FileStorage,
 }
 
 impl NodeContext {
+    /// Builds the context from `--config <path>`, the same TOML file the CLI
+    /// reads; without the flag the defaults are used.
     pub fn build() -> Result<Self, String> {
-        todo!("node context builder is not added yet")
+        let config_path = parse_config_path(env::args().skip(1))?;
+        Self::load(config_path.as_deref())
+    }
+
+    pub fn load(config_path: Option<&str>) -> Result<Self, String> {
+        let config = match config_path {
+            Some(path) => NodeConfig::from_file(path)
+                .map_err(|e| format!("не удалось загрузить {path}: {e}"))?,
+            None => NodeConfig::default(),
+        };
+
+        Ok(Self {
+            config,
+            storage: FileStorage::new(DEFAULT_CHAIN_PATH),
+        })
+    }
+}
+
+fn parse_config_path(mut args: impl Iterator<Item = String>) -> Result<Option<String>, String> {
+    let mut config_path = None;
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            let value = args
+                .next()
+                .ok_or_else(|| "ожидался путь после --config".to_string())?;
+            config_path = Some(value);
+            continue;
+        }
+
+        return Err(format!("лишний аргумент: {arg}"));
+    }
+    Ok(config_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use blockchain_core::types::{Address, Hash};
+
+    use super::*;
+
+    fn args(values: &[&str]) -> impl Iterator<Item = String> {
+        values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn config_path_comes_from_flag() {
+        assert_eq!(parse_config_path(args(&[])), Ok(None));
+        assert_eq!(
+            parse_config_path(args(&["--config", "node.toml"])),
+            Ok(Some("node.toml".to_string()))
+        );
+        assert!(parse_config_path(args(&["--config"])).is_err());
+        assert!(parse_config_path(args(&["mine"])).is_err());
+    }
+
+    #[test]
+    fn config_file_settings_reach_the_node() {
+        let path = env::temp_dir().join(format!("blockchain-node-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            r#"
+                miner_address = "0x0909090909090909090909090909090909090909"
+                checkpoints = [[1, "0x0101010101010101010101010101010101010101010101010101010101010101"]]
+                genesis_alloc = [["0x0202020202020202020202020202020202020202", 1000]]
+                max_transactions = 8
+                max_per_sender = 2
+            "#,
+        )
+        .unwrap();
+        let context = NodeContext::load(path.to_str());
+        let _ = fs::remove_file(&path);
+        let config = context.unwrap().config;
+
+        assert_eq!(config.miner_address, Address::from_bytes([9; 20]));
+        assert_eq!(config.checkpoints, [(1, Hash::from_bytes([1; 32]))]);
+        assert_eq!(config.genesis_alloc, [(Address::from_bytes([2; 20]), 1000)]);
+        assert_eq!(config.mempool.max_transactions, 8);
+        assert_eq!(config.mempool.max_per_sender, 2);
+    }
+
+    #[test]
+    fn unreadable_config_is_an_error() {
+        let path = env::temp_dir().join(format!("missing-node-{}.toml", std::process::id()));
+
+        assert!(NodeContext::load(path.to_str()).is_err());
     }
 }
@@ -1,7 +1,12 @@
+use blockchain_core::block::Block;
+use blockchain_core::transaction::Transaction;
+
 #[derive(Debug, Clone)]
 pub enum NodeEvent {
     Tick,
-    NewTransaction,
-    NewBlock,
+    // Produced by the network layer once it exists.
+    #[allow(dead_code)]
+    NewTransaction(Transaction),
+    NewBlock(Block),
     Shutdown,
 }
@@ -3,6 +3,14 @@ pub mod events;
 pub mod reconcile;
 pub mod state;
 
+use blockchain_core::blockchain::{Blockchain, OrphanPool};
+use blockchain_core::mempool::Mempool;
+use blockchain_core::types::Hash;
+
+use crate::mining::worker::MiningWorker;
+use crate::storage::bootstrap::load_or_init_chain;
+use crate::storage::flush_state;
+use crate::sync::orphans::handle_new_block;
 use context::NodeContext;
 use events::NodeEvent;
 use state::NodeState;
@@ -10,26 +18,84 @@ use state::NodeState;
 pub struct Node {
     pub state: NodeState,
     pub context: NodeContext,
+    pub chain: Blockchain,
+    pub mempool: Mempool,
+    pub orphans: OrphanPool,
+    pub miner: MiningWorker,
+    pub requested_blocks: Vec<Hash>,
 }
 
 impl Node {
     pub fn new() -> Result<Self, String> {
-        todo!("node initialization is not added yet")
+        Self::with_context(NodeContext::build()?)
+    }
+
+    pub fn with_context(context: NodeContext) -> Result<Self, String> {
+        let chain = load_or_init_chain(&context.config, &context.storage)?;
+        let miner = MiningWorker::new(&context.config)?;
+        miner.on_new_block(chain.height());
+
+        Ok(Self {
+            state: NodeState::new(chain.height()),
+            mempool: Mempool::with_config(context.config.mempool),
+            orphans: OrphanPool::new(context.config.orphan_pool),
+            miner,
+            chain,
+            context,
+            requested_blocks: Vec::new(),
+        })
     }
 
     pub fn handle_event(&mut self, event: NodeEvent) -> Result<(), String> {
-        let _ = (self, event);
-        todo!("event handling is not added yet")
+        match event {
+            NodeEvent::Tick => self.tick(),
+            NodeEvent::NewTransaction(transaction) => {
+                let hash = transaction.hash;
+                self.mempool
+                    .add_transaction(transaction, &self.chain)
+                    .map_err(|e| format!("транзакция {hash} отклонена: {e}"))
+            }
+            NodeEvent::NewBlock(block) => {
+                let accepted = handle_new_block(
+                    &mut self.chain,
+                    &mut self.mempool,
+                    &mut self.orphans,
+                    block,
+                    &mut self.requested_blocks,
+                )?;
+                if accepted.is_empty() {
+                    return Ok(());
+                }
+
+                self.state.height = self.chain.height();
+                self.miner.on_new_block(self.state.height);
+                flush_state(&self.chain, &self.context.storage)
+            }
+            NodeEvent::Shutdown => {
+                self.state.running = false;
+                self.miner.cancel();
+                flush_state(&self.chain, &self.context.storage)
+            }
+        }
     }
 
     pub fn tick(&mut self) -> Result<(), String> {
-        let _ = self;
-        todo!("node tick is not added yet")
+        let mut block = self
+            .chain
+            .block_template(&self.mempool)
+            .map_err(|e| format!("не удалось собрать шаблон блока: {e}"))?;
+        self.miner.mine_once(&mut block)?;
+        self.handle_event(NodeEvent::NewBlock(block))
     }
 
     pub fn run(&mut self) -> Result<(), String> {
-        let _ = self;
-        todo!("node loop is not added yet")
+        while self.state.running {
+            if let Err(err) = self.handle_event(NodeEvent::Tick) {
+                self.handle_event(NodeEvent::Shutdown)?;
+                return Err(err);
+            }
+        }
+        Ok(())
     }
 }
 
@@ -37,3 +103,40 @@ pub fn run() -> Result<(), String> {
     let mut node = Node::new()?;
     node.run()
 }
+
+#[cfg(test)]
+mod tests {
+    use blockchain_core::config::NodeConfig;
+    use blockchain_core::storage::FileStorage;
+
+    use super::*;
+
+    fn context(path: &str) -> NodeContext {
+        NodeContext {
+            config: NodeConfig {
+                mining_threads: 1,
+                ..NodeConfig::default()
+            },
+            storage: FileStorage::new(path),
+        }
+    }
+
+    #[test]
+    fn mined_blocks_are_persisted_and_restored() {
+        let path = std::env::temp_dir().join(format!("blockchain-node-{}.db", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+
+        let mut node = Node::with_context(context(&path)).unwrap();
+        node.handle_event(NodeEvent::Tick).unwrap();
+        node.handle_event(NodeEvent::Tick).unwrap();
+        node.handle_event(NodeEvent::Shutdown).unwrap();
+        let restored = Node::with_context(context(&path));
+        let _ = std::fs::remove_file(&path);
+
+        assert!(!node.state.running);
+        assert_eq!(node.state.height, 2);
+        let restored = restored.unwrap();
+        assert_eq!(restored.state.height, 2);
+        assert_eq!(restored.chain.tip().hash(), node.chain.tip().hash());
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 pub fn reconcile_chain() -> Result<(), String> {
     todo!("chain reconciliation is not added yet")
 }
@@ -4,7 +4,10 @@ pub struct NodeState {
 }
 
 impl NodeState {
-    pub fn new() -> Self {
-        todo!("node state constructor is not added yet")
+    pub fn new(height: u64) -> Self {
+        Self {
+            running: true,
+            height,
+        }
     }
 }
@@ -1,3 +1,13 @@
-pub fn load_or_init_chain() -> Result<(), String> {
-    todo!("storage bootstrap is not added yet")
+use blockchain_core::blockchain::Blockchain;
+use blockchain_core::config::NodeConfig;
+use blockchain_core::storage::ChainStorage;
+
+pub fn load_or_init_chain(
+    config: &NodeConfig,
+    storage: &impl ChainStorage,
+) -> Result<Blockchain, String> {
+    let stored = storage
+        .load()
+        .map_err(|e| format!("не удалось загрузить цепочку: {e}"))?;
+    Blockchain::restore(config, stored).map_err(|e| format!("сохранённая цепочка невалидна: {e}"))
 }
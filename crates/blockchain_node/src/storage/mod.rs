@@ -1,5 +1,10 @@
 pub mod bootstrap;
 
-pub fn flush_state() -> Result<(), String> {
-    todo!("state flush is not added yet")
+use blockchain_core::blockchain::Blockchain;
+use blockchain_core::storage::ChainStorage;
+
+pub fn flush_state(chain: &Blockchain, storage: &impl ChainStorage) -> Result<(), String> {
+    storage
+        .save(&chain.stored_blocks())
+        .map_err(|e| format!("не удалось сохранить цепочку: {e}"))
 }
@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 pub fn download_missing_blocks() -> Result<(), String> {
     todo!("block downloader is not added yet")
 }
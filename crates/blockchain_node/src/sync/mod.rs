@@ -1,2 +1,3 @@
 pub mod downloader;
+pub mod orphans;
 pub mod validator;
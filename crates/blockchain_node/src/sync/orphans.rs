@@ -0,0 +1,221 @@
+use blockchain_core::block::Block;
+use blockchain_core::blockchain::{Blockchain, OrphanPool};
+use blockchain_core::mempool::Mempool;
+use blockchain_core::types::Hash;
+
+use crate::consensus::reorg::apply_chain_update;
+
+pub trait AncestorRequester {
+    fn request_block(&mut self, hash: Hash);
+}
+
+/// Collects requested hashes until the network layer can fetch them.
+impl AncestorRequester for Vec<Hash> {
+    fn request_block(&mut self, hash: Hash) {
+        if !self.contains(&hash) {
+            self.push(hash);
+        }
+    }
+}
+
+pub fn handle_new_block(
+    chain: &mut Blockchain,
+    mempool: &mut Mempool,
+    orphans: &mut OrphanPool,
+    block: Block,
+    requester: &mut impl AncestorRequester,
+) -> Result<Vec<Hash>, String> {
+    let now = chain.clock.now();
+    orphans.expire(now);
+
+    let hash = block.hash();
+    if chain.index.contains(&hash) || orphans.contains(&hash) {
+        return Ok(Vec::new());
+    }
+
+    if !chain.index.contains(&block.header.parent_hash) {
+        chain
+            .consensus
+            .check_proof_of_work(&block.header)
+            .map_err(|e| format!("орфан {hash} отклонён: {e}"))?;
+        orphans.insert(block, now);
+        if let Some(missing) = orphans.missing_ancestor(&hash) {
+            requester.request_block(missing);
+        }
+        return Ok(Vec::new());
+    }
+
+    accept_block(chain, mempool, block)?;
+    let mut accepted = vec![hash];
+    let mut pending = vec![hash];
+    while let Some(parent) = pending.pop() {
+        for child in orphans.take_children(&parent) {
+            let child_hash = child.hash();
+            match accept_block(chain, mempool, child) {
+                Ok(()) => {
+                    accepted.push(child_hash);
+                    pending.push(child_hash);
+                }
+                Err(_) => {
+                    orphans.remove_with_descendants(&child_hash);
+                }
+            }
+        }
+    }
+
+    Ok(accepted)
+}
+
+fn accept_block(chain: &mut Blockchain, mempool: &mut Mempool, block: Block) -> Result<(), String> {
+    let hash = block.hash();
+    let update = chain
+        .add_block(block)
+        .map_err(|e| format!("блок {hash} отклонён: {e}"))?;
+    apply_chain_update(chain, mempool, &update);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use blockchain_core::config::NodeConfig;
+    use blockchain_core::consensus::CancelToken;
+    use blockchain_core::types::{FixedClock, SystemClock};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingRequester(Vec<Hash>);
+
+    impl AncestorRequester for RecordingRequester {
+        fn request_block(&mut self, hash: Hash) {
+            self.0.push(hash);
+        }
+    }
+
+    fn orphan(chain: &Blockchain, bits: u32) -> Block {
+        let mut block = chain.tip().clone();
+        block.header.number = 5;
+        block.header.parent_hash = Hash::keccak(b"unknown parent");
+        block.header.bits = bits;
+        block
+    }
+
+    fn seal(chain: &Blockchain, block: &mut Block) {
+        chain
+            .consensus
+            .mine_block(block, 1, &CancelToken::new(), chain.clock.as_ref())
+            .unwrap();
+    }
+
+    /// Builds the next block on `chain` ten seconds after its tip.
+    fn template(chain: &mut Blockchain) -> Block {
+        let now = chain.tip().header.timestamp + chain.config.target_block_time;
+        chain.clock = Arc::new(FixedClock(now));
+        chain.block_template(&Mempool::new()).unwrap()
+    }
+
+    fn mine_next(chain: &mut Blockchain) -> Block {
+        let mut block = template(chain);
+        seal(chain, &mut block);
+        chain.add_block(block.clone()).unwrap();
+        block
+    }
+
+    fn receive(
+        chain: &mut Blockchain,
+        orphans: &mut OrphanPool,
+        block: Block,
+        requester: &mut RecordingRequester,
+    ) -> Result<Vec<Hash>, String> {
+        handle_new_block(chain, &mut Mempool::new(), orphans, block, requester)
+    }
+
+    #[test]
+    fn orphan_without_proof_of_work_is_not_pooled() {
+        let config = NodeConfig::default();
+        let mut chain = Blockchain::new(&config).unwrap();
+        let mut orphans = OrphanPool::new(config.orphan_pool);
+        let mut requester = RecordingRequester::default();
+
+        for bits in [0x1d00_ffff, 0x2100_ffff] {
+            let block = orphan(&chain, bits);
+            assert!(receive(&mut chain, &mut orphans, block, &mut requester).is_err());
+        }
+        assert!(orphans.is_empty());
+        assert!(requester.0.is_empty());
+    }
+
+    #[test]
+    fn mined_orphan_is_pooled_and_parent_requested() {
+        let config = NodeConfig::default();
+        let mut chain = Blockchain::new(&config).unwrap();
+        let mut orphans = OrphanPool::new(config.orphan_pool);
+        let mut block = orphan(&chain, config.initial_bits);
+        chain
+            .consensus
+            .mine_block(&mut block, 1, &CancelToken::new(), &SystemClock)
+            .unwrap();
+        let parent = block.header.parent_hash;
+
+        let mut requester = RecordingRequester::default();
+        let accepted = receive(&mut chain, &mut orphans, block.clone(), &mut requester).unwrap();
+
+        assert!(accepted.is_empty());
+        assert!(orphans.contains(&block.hash()));
+        assert_eq!(requester.0, [parent]);
+    }
+
+    #[test]
+    fn pooled_descendants_connect_when_parent_arrives() {
+        let config = NodeConfig::default();
+        let mut producer = Blockchain::new(&config).unwrap();
+        let blocks: Vec<Block> = (0..3).map(|_| mine_next(&mut producer)).collect();
+        let mut chain = Blockchain::new(&config).unwrap();
+        let mut orphans = OrphanPool::new(config.orphan_pool);
+        let mut requester = RecordingRequester::default();
+
+        for block in [&blocks[2], &blocks[1]] {
+            let accepted = receive(&mut chain, &mut orphans, block.clone(), &mut requester);
+            assert_eq!(accepted, Ok(Vec::new()));
+        }
+        assert_eq!(requester.0, [blocks[1].hash(), blocks[0].hash()]);
+
+        let accepted = receive(&mut chain, &mut orphans, blocks[0].clone(), &mut requester);
+
+        assert_eq!(accepted, Ok(blocks.iter().map(Block::hash).collect()));
+        assert_eq!(chain.tip().hash(), blocks[2].hash());
+        assert!(orphans.is_empty());
+    }
+
+    #[test]
+    fn invalid_orphan_drops_its_descendants() {
+        let config = NodeConfig::default();
+        let mut producer = Blockchain::new(&config).unwrap();
+        let parent = mine_next(&mut producer);
+        let mut invalid = template(&mut producer);
+        invalid.header.state_root = Hash::zero();
+        seal(&producer, &mut invalid);
+        let mut child = invalid.clone();
+        child.header.parent_hash = invalid.hash();
+        child.header.number += 1;
+        child.header.timestamp = child.header.timestamp + config.target_block_time;
+        seal(&producer, &mut child);
+
+        let mut chain = Blockchain::new(&config).unwrap();
+        let mut orphans = OrphanPool::new(config.orphan_pool);
+        let mut requester = RecordingRequester::default();
+        for block in [&child, &invalid] {
+            receive(&mut chain, &mut orphans, block.clone(), &mut requester).unwrap();
+        }
+        assert_eq!(orphans.len(), 2);
+
+        let accepted = receive(&mut chain, &mut orphans, parent.clone(), &mut requester);
+
+        assert_eq!(accepted, Ok(vec![parent.hash()]));
+        assert_eq!(chain.tip().hash(), parent.hash());
+        assert!(orphans.is_empty());
+        assert!(!chain.index.contains(&child.hash()));
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 pub fn validate_downloaded_chain() -> Result<(), String> {
     todo!("downloaded chain validation is not added yet")
 }